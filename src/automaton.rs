@@ -0,0 +1,200 @@
+/// Iterated grid updates, e.g. for cellular automata or repeated removal until nothing changes.
+use crate::grid::{Grid, Point};
+
+/// How updates made during a step become visible to the rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Update {
+    /// The rule always sees the grid as it was at the start of the step.
+    Synchronous,
+    /// Updates are written immediately and are visible to cells examined later in the same step.
+    InPlace,
+}
+
+/// Which cells are re-examined around a changed cell in frontier mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Neighbourhood {
+    Four,
+    Eight,
+}
+
+/// Repeatedly applies a rule to the cells of a grid.
+///
+/// The rule receives the grid and a point and returns `Some(value)` if the cell changes to `value`,
+/// [`None`] if it stays as is. A rule must not report a change for a cell that keeps its value,
+/// otherwise [`Automaton::run`] never reaches a fixpoint.
+pub struct Automaton<T> {
+    grid: Grid<T>,
+    buffer: Option<Grid<T>>,
+    frontier: Option<Frontier>,
+    changed_per_step: Vec<usize>,
+}
+
+struct Frontier {
+    neighbourhood: Neighbourhood,
+    points: Vec<Point>,
+    queued: Grid<bool>,
+}
+
+impl<T: Clone> Automaton<T> {
+    pub fn new(grid: Grid<T>, update: Update) -> Self {
+        let buffer = match update {
+            Update::Synchronous => Some(grid.clone()),
+            Update::InPlace => None,
+        };
+        Self {
+            grid,
+            buffer,
+            frontier: None,
+            changed_per_step: vec![],
+        }
+    }
+
+    /// Only re-examines cells in the `neighbourhood` of cells changed by the previous step.
+    /// This only gives the same result as a full scan if the rule solely depends on that neighbourhood.
+    pub fn with_frontier(mut self, neighbourhood: Neighbourhood) -> Self {
+        self.frontier = Some(Frontier {
+            neighbourhood,
+            points: self.grid.points().collect(),
+            queued: self.grid.map(|_| true),
+        });
+        self
+    }
+
+    pub fn grid(&self) -> &Grid<T> {
+        &self.grid
+    }
+
+    pub fn into_grid(self) -> Grid<T> {
+        self.grid
+    }
+
+    /// The number of steps that changed at least one cell.
+    pub fn steps(&self) -> usize {
+        self.changed_per_step.len()
+    }
+
+    /// The number of changed cells for each step that changed at least one cell.
+    pub fn changed_per_step(&self) -> &[usize] {
+        &self.changed_per_step
+    }
+
+    /// The total number of cell changes over all steps.
+    pub fn total_changed(&self) -> usize {
+        self.changed_per_step.iter().sum()
+    }
+
+    /// Performs a single step and returns the number of changed cells.
+    pub fn step(&mut self, rule: impl Fn(&Grid<T>, Point) -> Option<T>) -> usize {
+        let candidates = match &mut self.frontier {
+            Some(frontier) => {
+                for point in &frontier.points {
+                    frontier.queued[*point] = false;
+                }
+                std::mem::take(&mut frontier.points)
+            }
+            None => self.grid.points().collect(),
+        };
+
+        let mut changed = vec![];
+
+        match &mut self.buffer {
+            Some(buffer) => {
+                for point in candidates {
+                    if let Some(value) = rule(&self.grid, point) {
+                        buffer[point] = value;
+                        changed.push(point);
+                    }
+                }
+                std::mem::swap(&mut self.grid, buffer);
+                // NOTE: only changed cells differ between both buffers, so syncing them is sufficient.
+                for point in &changed {
+                    buffer[*point] = self.grid[*point].clone();
+                }
+            }
+            None => {
+                for point in candidates {
+                    if let Some(value) = rule(&self.grid, point) {
+                        self.grid[point] = value;
+                        changed.push(point);
+                    }
+                }
+            }
+        }
+
+        if let Some(frontier) = &mut self.frontier {
+            for point in &changed {
+                let neighbours: Vec<Point> = match frontier.neighbourhood {
+                    Neighbourhood::Four => self.grid.neighbours4(*point).collect(),
+                    Neighbourhood::Eight => self.grid.neighbours8(*point).collect(),
+                };
+                for p in std::iter::once(*point).chain(neighbours) {
+                    if !frontier.queued[p] {
+                        frontier.queued[p] = true;
+                        frontier.points.push(p);
+                    }
+                }
+            }
+        }
+
+        if !changed.is_empty() {
+            self.changed_per_step.push(changed.len());
+        }
+        changed.len()
+    }
+
+    /// Steps until a fixpoint is reached and returns the number of steps that changed something.
+    pub fn run(&mut self, rule: impl Fn(&Grid<T>, Point) -> Option<T>) -> usize {
+        let steps_before = self.steps();
+        while self.step(&rule) > 0 {}
+        self.steps() - steps_before
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Automaton, Neighbourhood, Update};
+    use crate::grid::{Grid, Point};
+
+    fn get_mock_grid() -> Grid<bool> {
+        Grid::parse("#....\n.....\n.....\n", |c| Some(c == '#')).unwrap()
+    }
+
+    /// Every cell next to a live cell comes alive.
+    fn spread(grid: &Grid<bool>, point: Point) -> Option<bool> {
+        (!grid[point] && grid.neighbours4(point).any(|p| grid[p])).then_some(true)
+    }
+
+    #[test]
+    fn steps_synchronously() {
+        let mut automaton = Automaton::new(get_mock_grid(), Update::Synchronous);
+        assert_eq!(automaton.step(spread), 2);
+        assert_eq!(automaton.step(spread), 3);
+        assert_eq!(automaton.run(spread), 4);
+        assert_eq!(automaton.steps(), 6);
+        assert_eq!(automaton.changed_per_step(), &[2, 3, 3, 3, 2, 1]);
+        assert_eq!(automaton.total_changed(), 14);
+        assert!(automaton.grid().iter().all(|(_, v)| *v));
+    }
+
+    #[test]
+    fn steps_in_place() {
+        let mut automaton = Automaton::new(get_mock_grid(), Update::InPlace);
+        // NOTE: row-major scanning lets a change propagate right and down within one step.
+        assert_eq!(automaton.step(spread), 14);
+        assert_eq!(automaton.run(spread), 0);
+        assert_eq!(automaton.steps(), 1);
+    }
+
+    #[test]
+    fn frontier_matches_full_scan() {
+        let mut full = Automaton::new(get_mock_grid(), Update::Synchronous);
+        let mut frontier =
+            Automaton::new(get_mock_grid(), Update::Synchronous).with_frontier(Neighbourhood::Four);
+        full.run(spread);
+        frontier.run(spread);
+        assert_eq!(full.changed_per_step(), frontier.changed_per_step());
+        assert_eq!(full.grid(), frontier.grid());
+    }
+}
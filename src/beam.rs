@@ -0,0 +1,118 @@
+/// Row-by-row propagation of beams that flow downward through a [`Grid`].
+use crate::grid::{Grid, Point};
+
+/// How a cell affects a beam entering it from above.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flow {
+    /// The beam continues downward.
+    Pass,
+    /// The beam is split into two beams continuing from the left and right neighbour of the cell.
+    /// Beams that would leave the grid are dropped.
+    Split,
+    /// The beam ends.
+    Absorb,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Propagation {
+    /// The number of distinct splitting cells reached by at least one beam.
+    pub splits: u64,
+    /// The number of distinct paths a single beam can take from the start to the bottom row.
+    pub timelines: u64,
+}
+
+/// Sends a beam downward from `start` and counts splits and timelines in a single pass over the rows.
+/// Only keeps the number of timelines per column of the current row, so memory is `O(width)`.
+/// The start cell itself is not classified. Counts saturate at `u64::MAX`.
+pub fn propagate<T>(grid: &Grid<T>, start: Point, classify: impl Fn(&T) -> Flow) -> Propagation {
+    if !grid.contains(start) {
+        return Propagation::default();
+    }
+
+    let mut splits = 0;
+    let mut current = vec![0u64; grid.width()];
+    let mut next = vec![0u64; grid.width()];
+    current[start.col] = 1;
+
+    for row in grid.rows().skip(start.row + 1) {
+        next.fill(0);
+        for (col, &count) in current.iter().enumerate() {
+            if count == 0 {
+                continue;
+            }
+            match classify(&row[col]) {
+                Flow::Pass => next[col] = next[col].saturating_add(count),
+                Flow::Split => {
+                    splits += 1;
+                    for target in [col.checked_sub(1), Some(col + 1)].into_iter().flatten() {
+                        if let Some(cell) = next.get_mut(target) {
+                            *cell = cell.saturating_add(count);
+                        }
+                    }
+                }
+                Flow::Absorb => {}
+            }
+        }
+        std::mem::swap(&mut current, &mut next);
+    }
+
+    Propagation {
+        splits,
+        timelines: current.into_iter().fold(0, u64::saturating_add),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Flow, Propagation, propagate};
+    use crate::grid::{Grid, Point};
+
+    fn classify(c: &char) -> Flow {
+        match c {
+            '^' => Flow::Split,
+            '#' => Flow::Absorb,
+            _ => Flow::Pass,
+        }
+    }
+
+    fn get_mock_grid(input: &str) -> Grid<char> {
+        Grid::parse(input, Some).unwrap()
+    }
+
+    #[test]
+    fn counts_splits_and_timelines() {
+        let grid = get_mock_grid("..S..\n.....\n..^..\n.....\n.^.^.\n.....\n");
+        let result = propagate(&grid, Point::new(0, 2), classify);
+        assert_eq!(
+            result,
+            Propagation {
+                splits: 3,
+                timelines: 4
+            }
+        );
+    }
+
+    #[test]
+    fn drops_beams_leaving_the_grid() {
+        let grid = get_mock_grid("S.\n^.\n#.\n");
+        let result = propagate(&grid, Point::new(0, 0), classify);
+        assert_eq!(result.splits, 1);
+        assert_eq!(result.timelines, 1);
+
+        let result = propagate(&grid, Point::new(1, 0), classify);
+        assert_eq!(result.timelines, 0);
+        assert_eq!(
+            propagate(&grid, Point::new(5, 0), classify),
+            Propagation::default()
+        );
+    }
+
+    #[test]
+    fn handles_tall_grids() {
+        let grid = Grid::new(1, 200_000, '.');
+        let result = propagate(&grid, Point::new(0, 0), classify);
+        assert_eq!(result.timelines, 1);
+    }
+}
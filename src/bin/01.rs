@@ -1,29 +1,53 @@
-use nom::{
-    IResult, Parser,
-    branch::alt,
-    character::complete::{char, digit1, newline},
-    combinator::{all_consuming, map_res},
-    multi::separated_list0,
-};
+use advent_of_code::parsers::{ParseError, lines0, parse_all, unsigned};
+use advent_of_code::random::Rng;
+use nom::{IResult, Parser, branch::alt, character::complete::char, combinator::value};
 
-advent_of_code::solution!(1);
+advent_of_code::solution!(1, reference);
 
-type Rotation = (i32, u64);
+/// The number of positions on the dial, `0` to `99`.
+const DIAL_SIZE: u64 = 100;
+const START: u64 = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Rotation {
+    direction: Direction,
+    clicks: u64,
+}
+
+impl Rotation {
+    /// The dial position after applying the rotation at `position`.
+    fn apply(self, position: u64) -> u64 {
+        let clicks = self.clicks % DIAL_SIZE;
+        match self.direction {
+            Direction::Left => (position + DIAL_SIZE - clicks) % DIAL_SIZE,
+            Direction::Right => (position + clicks) % DIAL_SIZE,
+        }
+    }
+
+    /// How many clicks of the rotation starting at `position` leave the dial at `0`.
+    fn zero_crossings(self, position: u64) -> u64 {
+        // NOTE: turning left from `position` is turning right from the mirrored position.
+        let distance = match self.direction {
+            Direction::Left => (DIAL_SIZE - position) % DIAL_SIZE,
+            Direction::Right => position,
+        };
+        self.clicks / DIAL_SIZE + (distance + self.clicks % DIAL_SIZE) / DIAL_SIZE
+    }
+}
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let mut val: i32 = 50;
+    let rotations = parse_entire_input(input).ok()?;
+    let mut position = START;
     let mut counter: u64 = 0;
-    let (_, rotations) = parse_entire_input(input).ok()?;
-    for (dir, clicks) in rotations {
-        for _ in 0..clicks {
-            val += dir;
-            if val == 100 {
-                val = 0;
-            } else if val == -1 {
-                val = 99;
-            }
-        }
-        if val == 0 {
+    for rotation in rotations {
+        position = rotation.apply(position);
+        if position == 0 {
             counter += 1;
         }
     }
@@ -31,42 +55,76 @@ pub fn part_one(input: &str) -> Option<u64> {
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let mut val: i32 = 50;
+    let rotations = parse_entire_input(input).ok()?;
+    let mut position = START;
     let mut counter: u64 = 0;
-    let (_, rotations) = parse_entire_input(input).ok()?;
-    for (dir, clicks) in rotations {
-        for _ in 0..clicks {
-            val += dir;
-            if val == 100 {
-                val = 0;
-            } else if val == -1 {
-                val = 99;
-            }
-            if val == 0 {
-                counter += 1;
-            }
-        }
+    for rotation in rotations {
+        counter += rotation.zero_crossings(position);
+        position = rotation.apply(position);
     }
     Some(counter)
 }
 
-fn parse_entire_input(input: &str) -> IResult<&str, Vec<Rotation>> {
-    all_consuming(separated_list0(newline, parse_single_line)).parse(input)
+pub fn part_one_reference(input: &str) -> Option<u64> {
+    Some(click_by_click(input)?.0)
 }
 
-fn parse_single_line(input: &str) -> IResult<&str, Rotation> {
-    let (input, dir) = alt((char('L'), char('R'))).parse(input)?;
-    let (input, number) = decimal_value(input)?;
+pub fn part_two_reference(input: &str) -> Option<u64> {
+    Some(click_by_click(input)?.1)
+}
 
-    match dir {
-        'L' => Ok((input, (-1, number))),
-        'R' => Ok((input, (1, number))),
-        _ => Ok((input, (0, number))), // unreachable
+/// Turns the dial one click at a time, counting the rotations and the clicks that end at `0`.
+fn click_by_click(input: &str) -> Option<(u64, u64)> {
+    let rotations = parse_entire_input(input).ok()?;
+    let mut position = START;
+    let (mut rotations_at_zero, mut clicks_at_zero) = (0, 0);
+    for rotation in rotations {
+        for _ in 0..rotation.clicks {
+            position = match rotation.direction {
+                Direction::Left => (position + DIAL_SIZE - 1) % DIAL_SIZE,
+                Direction::Right => (position + 1) % DIAL_SIZE,
+            };
+            if position == 0 {
+                clicks_at_zero += 1;
+            }
+        }
+        if position == 0 {
+            rotations_at_zero += 1;
+        }
     }
+    Some((rotations_at_zero, clicks_at_zero))
+}
+
+fn parse_entire_input(input: &str) -> Result<Vec<Rotation>, ParseError> {
+    parse_all(lines0(parse_single_line), input)
+}
+
+fn parse_single_line(input: &str) -> IResult<&str, Rotation> {
+    (
+        alt((
+            value(Direction::Left, char('L')),
+            value(Direction::Right, char('R')),
+        )),
+        unsigned,
+    )
+        .map(|(direction, clicks)| Rotation { direction, clicks })
+        .parse(input)
 }
 
-fn decimal_value(input: &str) -> IResult<&str, u64> {
-    map_res(digit1, str::parse).parse(input)
+/// Generates `size` rotations, mostly small with some spanning many full turns.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    (0..size)
+        .map(|_| {
+            let direction = if rng.chance(0.5) { 'L' } else { 'R' };
+            let clicks = if rng.chance(0.9) {
+                rng.range(1..=99)
+            } else {
+                rng.range(100..=100_000)
+            };
+            format!("{direction}{clicks}\n")
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -84,4 +142,40 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(6));
     }
+
+    #[test]
+    fn test_empty_input() {
+        assert_eq!(part_one(""), Some(0));
+        assert_eq!(part_two(""), Some(0));
+        assert_eq!(part_two("\n"), Some(0));
+    }
+
+    #[test]
+    fn test_generate() {
+        let rotations = parse_entire_input(&generate(1, 100)).unwrap();
+        assert_eq!(rotations.len(), 100);
+        assert!(rotations.iter().all(|r| r.clicks >= 1));
+        // a few rotations span many full turns of the dial.
+        assert!(rotations.iter().any(|r| r.clicks >= 100));
+    }
+
+    #[test]
+    fn test_reference() {
+        crosscheck(10, 20);
+    }
+
+    #[cfg(feature = "test_lib")]
+    #[test]
+    fn test_part_two_counts_every_stop_at_zero() {
+        use advent_of_code::property::{check, int, vec};
+
+        let rotations = vec((int(0u8..=1), int(1u32..=350)), 1..=30);
+        check(&rotations, |rotations| {
+            let input: String = rotations
+                .iter()
+                .map(|&(left, clicks)| format!("{}{clicks}\n", if left == 1 { 'L' } else { 'R' }))
+                .collect();
+            part_two(&input) >= part_one(&input)
+        });
+    }
 }
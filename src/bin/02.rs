@@ -1,84 +1,83 @@
-use nom::{
-    IResult, Parser,
-    character::complete::{char, digit1},
-    combinator::{all_consuming, map_res},
-    multi::separated_list0,
-    sequence::separated_pair,
-};
+use std::ops::RangeInclusive;
+
+use advent_of_code::parsers::{ParseError, comma_list0, parse_all, unsigned};
+use advent_of_code::random::Rng;
+use nom::{Parser, character::complete::char, sequence::separated_pair};
 
 advent_of_code::solution!(2);
 
+/// IDs fit into `u64`, so they have at most this many digits.
+const MAX_DIGITS: u32 = 20;
+
 pub fn part_one(input: &str) -> Option<u64> {
-    let mut counter: u64 = 0;
-    let (_, ranges) = parse_entire_input(input).ok()?;
-    for range in ranges {
-        for n in range.0..=range.1 {
-            if is_invalid_id(n) {
-                counter += n;
-            }
+    let ranges = parse_entire_input(input).ok()?;
+    let mut counter: u128 = 0;
+    for range in &ranges {
+        for digits in (2..=MAX_DIGITS).step_by(2) {
+            counter += sum_repeated(range, digits, digits / 2);
         }
     }
-    Some(counter)
+    u64::try_from(counter).ok()
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let mut counter: u64 = 0;
-    let (_, ranges) = parse_entire_input(input).ok()?;
-    for range in ranges {
-        for n in range.0..=range.1 {
-            if is_invalid_id2(n) {
-                counter += n;
+    let ranges = parse_entire_input(input).ok()?;
+    let mut counter: u128 = 0;
+    for range in &ranges {
+        for digits in 2..=MAX_DIGITS {
+            // NOTE: an ID made of a block of `len` digits is also made of any block whose length is a
+            // multiple of `len`, so `exact[len]` only keeps IDs whose shortest repeating block has `len` digits.
+            let mut exact = vec![0u128; digits as usize];
+            for len in (1..digits).filter(|len| digits.is_multiple_of(*len)) {
+                let shorter: u128 = (1..len)
+                    .filter(|l| len.is_multiple_of(*l))
+                    .map(|l| exact[l as usize])
+                    .sum();
+                exact[len as usize] = sum_repeated(range, digits, len) - shorter;
+                counter += exact[len as usize];
             }
         }
     }
-    Some(counter)
+    u64::try_from(counter).ok()
 }
 
-fn is_invalid_id(id: u64) -> bool {
-    let num_digits = id.checked_ilog10().unwrap_or(0) + 1;
-    if !num_digits.is_multiple_of(2) {
-        return false;
-    }
-    let div = 10u64.pow(num_digits / 2);
-    let left = id / div;
-    let right = id % div;
-    left == right
-}
+/// Sums the IDs in `range` with `digits` digits that consist of a block of `len` digits repeated.
+/// Such an ID is the block times `10^(digits - len) + ... + 10^len + 1`, so the blocks form a range too.
+fn sum_repeated(range: &RangeInclusive<u64>, digits: u32, len: u32) -> u128 {
+    let pow10 = |exp: u32| 10u128.pow(exp);
+    let multiplier = (pow10(digits) - 1) / (pow10(len) - 1);
 
-fn is_invalid_id2(id: u64) -> bool {
-    let num_digits = id.checked_ilog10().unwrap_or(0) + 1;
-    for num_chunks in 2..=num_digits {
-        if !num_digits.is_multiple_of(num_chunks) {
-            continue;
-        }
-        let chunk_size = num_digits / num_chunks;
-        let div = 10u64.pow(chunk_size);
-        let mut all_equal = true;
-        let first_chunk = id % div;
-        for chunk_index in 1..num_chunks {
-            let chunk = (id / 10u64.pow(chunk_size * chunk_index)) % div;
-            if chunk != first_chunk {
-                all_equal = false;
-                break;
-            }
-        }
-        if all_equal {
-            return true;
-        }
+    let low = u128::from(*range.start()).max(pow10(digits - 1));
+    let high = u128::from(*range.end()).min(pow10(digits) - 1);
+    let first_block = low.div_ceil(multiplier).max(pow10(len - 1));
+    let last_block = (high / multiplier).min(pow10(len) - 1);
+    if first_block > last_block {
+        return 0;
     }
-    false
-}
-
-fn parse_entire_input(input: &str) -> IResult<&str, Vec<(u64, u64)>> {
-    all_consuming(separated_list0(char(','), parse_single_line)).parse(input)
+    multiplier * (first_block + last_block) * (last_block - first_block + 1) / 2
 }
 
-fn parse_single_line(input: &str) -> IResult<&str, (u64, u64)> {
-    separated_pair(decimal_value, char('-'), decimal_value).parse(input)
+/// Every range counts on its own, so IDs in overlapping ranges count once per range.
+/// A reversed range such as `5-3` is empty and contributes nothing.
+fn parse_entire_input(input: &str) -> Result<Vec<RangeInclusive<u64>>, ParseError> {
+    parse_all(
+        comma_list0(separated_pair(unsigned, char('-'), unsigned).map(|(start, end)| start..=end)),
+        input,
+    )
 }
 
-fn decimal_value(input: &str) -> IResult<&str, u64> {
-    map_res(digit1, str::parse).parse(input)
+/// Generates `size` ranges of IDs with up to 12 digits and up to a billion values each.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let ranges: Vec<_> = (0..size)
+        .map(|_| {
+            let digits = rng.range(1..=12) as u32;
+            let start = rng.range(10u64.pow(digits - 1)..=10u64.pow(digits) - 1);
+            let width = 10u64.pow(rng.range(0..=9) as u32);
+            format!("{start}-{}", start + rng.below(width))
+        })
+        .collect();
+    format!("{}\n", ranges.join(","))
 }
 
 #[cfg(test)]
@@ -96,4 +95,32 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(4174379265));
     }
+
+    #[test]
+    fn test_empty_input() {
+        assert_eq!(part_one(""), Some(0));
+        assert_eq!(part_two(""), Some(0));
+        assert_eq!(part_two("\n"), Some(0));
+    }
+
+    #[test]
+    fn test_ranges_count_separately() {
+        assert_eq!(part_one("11-22"), Some(33));
+        assert_eq!(part_one("11-22,22-33"), Some(33 + 22 + 33));
+        assert_eq!(part_two("11-22,22-33"), Some(33 + 22 + 33));
+        assert_eq!(part_one("22-11,11-22"), Some(33));
+        assert_eq!(part_two("22-11"), Some(0));
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(1, 50);
+        assert_eq!(input.lines().count(), 1);
+        let ranges = parse_entire_input(&input).unwrap();
+        assert_eq!(ranges.len(), 50);
+        assert!(ranges.iter().all(|r| r.start() <= r.end()));
+        // some ranges are far too wide to check every ID.
+        assert!(ranges.iter().any(|r| r.end() - r.start() >= 100_000_000));
+        assert!(ranges.iter().any(|r| r.start().to_string().len() >= 11));
+    }
 }
@@ -1,17 +1,14 @@
-advent_of_code::solution!(3);
+advent_of_code::solution!(3, reference);
 
-use nom::{
-    IResult, Parser,
-    character::complete::{newline, one_of},
-    combinator::{all_consuming, map_opt},
-    multi::{many1, separated_list0},
-};
+use advent_of_code::parsers::{ParseError, lines0, parse_all};
+use advent_of_code::random::Rng;
+use nom::{IResult, Parser, character::complete::one_of, combinator::map_opt, multi::many1};
 
 type Bank = Vec<u32>;
 
 pub fn part_one(input: &str) -> Option<u64> {
     let mut joltage: u64 = 0;
-    let (_, banks) = parse_entire_input(input).ok()?;
+    let banks = parse_entire_input(input).ok()?;
     const MAX_BATTERY_CAPACITY: usize = 2;
     for bank in banks {
         let mut max_joltage = [0; MAX_BATTERY_CAPACITY];
@@ -38,7 +35,7 @@ pub fn part_one(input: &str) -> Option<u64> {
 
 pub fn part_two(input: &str) -> Option<u64> {
     let mut joltage: u64 = 0;
-    let (_, banks) = parse_entire_input(input).ok()?;
+    let banks = parse_entire_input(input).ok()?;
     const MAX_BATTERY_CAPACITY: usize = 12;
     for bank in banks {
         let mut max_joltage = [0; MAX_BATTERY_CAPACITY];
@@ -64,14 +61,73 @@ pub fn part_two(input: &str) -> Option<u64> {
     Some(joltage)
 }
 
-fn parse_entire_input(input: &str) -> IResult<&str, Vec<Bank>> {
-    all_consuming(separated_list0(newline, parse_single_line)).parse(input)
+/// Tries every pair of batteries.
+pub fn part_one_reference(input: &str) -> Option<u64> {
+    let banks = parse_entire_input(input).ok()?;
+    let joltages = banks.iter().map(|bank| {
+        (0..bank.len())
+            .flat_map(|i| (i + 1..bank.len()).map(move |j| u64::from(bank[i] * 10 + bank[j])))
+            .max()
+            .unwrap_or(0)
+    });
+    Some(joltages.sum())
+}
+
+/// Keeps the largest joltage for every number of batteries turned on so far.
+pub fn part_two_reference(input: &str) -> Option<u64> {
+    const MAX_BATTERY_CAPACITY: usize = 12;
+    let banks = parse_entire_input(input).ok()?;
+    let joltages = banks.iter().map(|bank| {
+        let mut best = [None; MAX_BATTERY_CAPACITY + 1];
+        best[0] = Some(0u64);
+        for &battery in bank {
+            for len in (1..=MAX_BATTERY_CAPACITY).rev() {
+                if let Some(prefix) = best[len - 1] {
+                    best[len] = best[len].max(Some(prefix * 10 + u64::from(battery)));
+                }
+            }
+        }
+        best[MAX_BATTERY_CAPACITY].unwrap_or(0)
+    });
+    Some(joltages.sum())
+}
+
+fn parse_entire_input(input: &str) -> Result<Vec<Bank>, ParseError> {
+    parse_all(lines0(parse_single_line), input)
 }
 
 fn parse_single_line(input: &str) -> IResult<&str, Bank> {
     many1(map_opt(one_of("0123456789"), |c| c.to_digit(10))).parse(input)
 }
 
+/// Generates `size` banks of 100 batteries each.
+/// Digits are mostly low and often repeated, with the largest ones placed near the end of the bank,
+/// so that the greedy choice has to leave enough batteries for the remaining slots.
+pub fn generate(seed: u64, size: usize) -> String {
+    const BANK_LEN: usize = 100;
+    let mut rng = Rng::new(seed);
+    (0..size)
+        .map(|_| {
+            let mut bank: Vec<u8> = Vec::with_capacity(BANK_LEN);
+            for _ in 0..BANK_LEN {
+                let digit = match bank.last() {
+                    Some(&previous) if rng.chance(0.4) => previous,
+                    _ if rng.chance(0.8) => 1 + rng.below(4) as u8,
+                    _ => 1 + rng.below(9) as u8,
+                };
+                bank.push(digit);
+            }
+            for _ in 0..=rng.below(3) {
+                let position = BANK_LEN - 1 - rng.index(15);
+                bank[position] = 9 - rng.below(2) as u8;
+            }
+            let mut bank: String = bank.iter().map(|&d| char::from(b'0' + d)).collect();
+            bank.push('\n');
+            bank
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -87,4 +143,28 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(3121910778619));
     }
+
+    #[test]
+    fn test_empty_input() {
+        assert_eq!(part_one(""), Some(0));
+        assert_eq!(part_two(""), Some(0));
+        assert_eq!(part_two("\n"), Some(0));
+    }
+
+    #[test]
+    fn test_generate() {
+        let banks = parse_entire_input(&generate(1, 50)).unwrap();
+        assert_eq!(banks.len(), 50);
+        for bank in &banks {
+            assert_eq!(bank.len(), 100);
+            assert!(bank.iter().filter(|&&battery| battery <= 4).count() > 50);
+            // the largest batteries are among the last ones, where not every slot can still take them.
+            assert!(bank[85..].iter().any(|&battery| battery >= 8));
+        }
+    }
+
+    #[test]
+    fn test_reference() {
+        crosscheck(10, 20);
+    }
 }
@@ -1,106 +1,54 @@
-use nom::{
-    IResult, Parser,
-    branch::alt,
-    character::complete::{char, newline},
-    combinator::{all_consuming, map_opt},
-    multi::{many1, separated_list0},
-};
+use advent_of_code::automaton::{Automaton, Neighbourhood, Update};
+use advent_of_code::grid::{Grid, Point};
+use advent_of_code::random::Rng;
 
 advent_of_code::solution!(4);
 
-const DIRECTION: [(isize, isize); 8] = [
-    (0, -1),  // Left
-    (0, 1),   // Right
-    (1, -1),  // Down-Left
-    (1, 0),   // Down
-    (1, 1),   // Down-Right
-    (-1, -1), // Up-Left
-    (-1, 0),  // Up
-    (-1, 1),  // Up-Right
-];
-
 pub fn part_one(input: &str) -> Option<u64> {
-    let (_, grid) = parse_entire_input(input).ok()?;
+    let grid = parse_entire_input(input)?;
 
-    let mut counter = 0;
-    for (row_index, row) in grid.iter().enumerate() {
-        for (col_index, &cell) in row.iter().enumerate() {
-            if !cell {
-                continue;
-            }
-            let mut cell_counter = 0;
-            for (dr, dc) in DIRECTION {
-                let r = row_index as isize + dr;
-                let c = col_index as isize + dc;
-                if r < 0 || c < 0 || r >= grid.len() as isize || c >= row.len() as isize {
-                    continue;
-                }
-                if grid[r as usize][c as usize] {
-                    cell_counter += 1;
-                }
-            }
-            if cell_counter < 4 {
-                counter += 1;
-            }
-        }
-    }
+    let counter = grid
+        .points()
+        .filter(|&point| grid[point] && is_accessible(&grid, point))
+        .count();
 
-    Some(counter)
+    Some(counter as u64)
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let (_, mut grid) = parse_entire_input(input).ok()?;
+    let grid = parse_entire_input(input)?;
 
-    let mut counter = 0;
+    let mut automaton =
+        Automaton::new(grid, Update::Synchronous).with_frontier(Neighbourhood::Eight);
+    automaton.run(|grid, point| (grid[point] && is_accessible(grid, point)).then_some(false));
 
-    loop {
-        let previous_grid = grid.clone();
-        let mut changed = false;
-        for (row_index, row) in grid.iter_mut().enumerate() {
-            for (col_index, cell) in row.iter_mut().enumerate() {
-                if !*cell {
-                    continue;
-                }
-                let mut cell_counter = 0;
-                for (dr, dc) in DIRECTION {
-                    let r = row_index as isize + dr;
-                    let c = col_index as isize + dc;
-                    if r < 0
-                        || c < 0
-                        || r >= previous_grid.len() as isize
-                        || c >= previous_grid[0].len() as isize
-                    {
-                        continue;
-                    }
-                    if previous_grid[r as usize][c as usize] {
-                        cell_counter += 1;
-                    }
-                }
-                if cell_counter < 4 {
-                    counter += 1;
-                    *cell = false;
-                    changed = true;
-                }
-            }
-        }
-        if !changed {
-            break;
-        }
-    }
-    Some(counter)
+    Some(automaton.total_changed() as u64)
 }
 
-fn parse_entire_input(input: &str) -> IResult<&str, Vec<Vec<bool>>> {
-    all_consuming(separated_list0(newline, parse_single_line)).parse(input)
+fn is_accessible(grid: &Grid<bool>, point: Point) -> bool {
+    grid.neighbours8(point).filter(|&p| grid[p]).count() < 4
 }
 
-fn parse_single_line(input: &str) -> IResult<&str, Vec<bool>> {
-    many1(map_opt(alt((char('.'), char('@'))), |c| match c {
+fn parse_entire_input(input: &str) -> Option<Grid<bool>> {
+    Grid::parse(input, |c| match c {
         '.' => Some(false),
         '@' => Some(true),
         _ => None,
-    }))
-    .parse(input)
+    })
+}
+
+/// Generates a `size` × `size` grid where most cells hold a roll of paper.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    (0..size)
+        .map(|_| {
+            let mut row: String = (0..size)
+                .map(|_| if rng.chance(0.6) { '@' } else { '.' })
+                .collect();
+            row.push('\n');
+            row
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -118,4 +66,14 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(43));
     }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(1, 30);
+        let grid = parse_entire_input(&input).unwrap();
+        assert_eq!((grid.width(), grid.height()), (30, 30));
+        let rolls = grid.iter().filter(|&(_, &roll)| roll).count();
+        assert!((450..=630).contains(&rolls));
+        assert!(part_two(&input) >= part_one(&input));
+    }
 }
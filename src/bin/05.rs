@@ -1,91 +1,90 @@
+use advent_of_code::parsers::{ParseError, lines, parse_all, unsigned};
+use advent_of_code::random::Rng;
+use advent_of_code::range_set::{RangeSet, parse_range, parse_range_list};
 use nom::{
-    IResult, Parser,
-    character::complete::{char, digit1, newline},
-    combinator::{all_consuming, map_res},
-    multi::many0,
+    character::complete::newline,
     sequence::{separated_pair, terminated},
 };
+use std::ops::RangeInclusive;
 
-advent_of_code::solution!(5);
-
-type Range = (u64, u64);
-
-fn merge_ranges(ranges: Vec<Range>) -> Vec<Range> {
-    if ranges.is_empty() {
-        return vec![];
-    }
-    let mut sorted_ranges = ranges.clone();
-    sorted_ranges.sort();
-    let mut merged: Vec<(u64, u64)> = vec![];
-    let mut current_range = sorted_ranges[0];
-    for range in sorted_ranges.into_iter().skip(1) {
-        if range.0 <= current_range.1 + 1 {
-            current_range.1 = current_range.1.max(range.1);
-        } else {
-            merged.push(current_range);
-            current_range = range;
-        }
-    }
-    merged.push(current_range);
-    merged
-}
+advent_of_code::solution!(5, reference);
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let (_, (ranges, ids)) = parse_entire_input(input).ok()?;
-
-    let merged_ranges = merge_ranges(ranges);
-    let mut counter: u64 = 0;
-    for id in ids {
-        let search_result = merged_ranges.binary_search_by(|probe| {
-            if probe.1 < id {
-                std::cmp::Ordering::Less
-            } else if probe.0 > id {
-                std::cmp::Ordering::Greater
-            } else {
-                std::cmp::Ordering::Equal
-            }
-        });
-        if search_result.is_ok() {
-            counter += 1;
-        }
-    }
-    Some(counter)
+    let (fresh, ids) = parse_entire_input(input).ok()?;
+
+    Some(ids.into_iter().filter(|&id| fresh.contains(id)).count() as u64)
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let (_, (ranges, _)) = parse_entire_input(input).ok()?;
+    let (fresh, _) = parse_entire_input(input).ok()?;
 
-    let merged_ranges = merge_ranges(ranges);
-    let mut counter: u64 = 0;
-    for range in merged_ranges {
-        counter += range.1 - range.0 + 1;
-    }
-    Some(counter)
+    Some(fresh.total_len())
 }
 
-fn parse_entire_input(input: &str) -> IResult<&str, (Vec<Range>, Vec<u64>)> {
-    all_consuming(separated_pair(
-        many0(parse_ranges),
-        newline,
-        many0(parse_ids),
-    ))
-    .parse(input)
+/// Checks every ID against every range, without merging them.
+pub fn part_one_reference(input: &str) -> Option<u64> {
+    let (ranges, ids) = parse_unmerged_input(input).ok()?;
+    let fresh = ids
+        .iter()
+        .filter(|id| ranges.iter().any(|range| range.contains(id)));
+    Some(fresh.count() as u64)
+}
+
+/// Sweeps over the ranges sorted by their start, only counting values past the ones covered so far.
+pub fn part_two_reference(input: &str) -> Option<u64> {
+    let (mut ranges, _) = parse_unmerged_input(input).ok()?;
+    ranges.sort_unstable_by_key(|range| *range.start());
+    let mut total: u64 = 0;
+    let mut covered: Option<u64> = None;
+    for range in ranges {
+        let start = match covered {
+            Some(end) if end >= *range.start() => end.checked_add(1),
+            _ => Some(*range.start()),
+        };
+        if let Some(start) = start.filter(|start| start <= range.end()) {
+            total = total.saturating_add(range.end() - start + 1);
+            covered = Some(*range.end());
+        }
+    }
+    Some(total)
 }
 
-fn parse_ranges(input: &str) -> IResult<&str, Range> {
-    terminated(
-        separated_pair(parse_number, char('-'), parse_number),
-        newline,
+fn parse_unmerged_input(input: &str) -> Result<(Vec<RangeInclusive<u64>>, Vec<u64>), ParseError> {
+    parse_all(
+        separated_pair(
+            terminated(lines(parse_range), newline),
+            newline,
+            lines(unsigned),
+        ),
+        input,
     )
-    .parse(input)
 }
 
-fn parse_ids(input: &str) -> IResult<&str, u64> {
-    terminated(parse_number, newline).parse(input)
+fn parse_entire_input(input: &str) -> Result<(RangeSet, Vec<u64>), ParseError> {
+    parse_all(
+        separated_pair(
+            terminated(parse_range_list('\n'), newline),
+            newline,
+            lines(unsigned),
+        ),
+        input,
+    )
 }
 
-fn parse_number(input: &str) -> IResult<&str, u64> {
-    map_res(digit1, str::parse).parse(input)
+/// Generates `size` possibly overlapping fresh ranges followed by `size` ingredient IDs.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut input = String::new();
+    for _ in 0..size {
+        let start = rng.range(1..=1_000_000_000_000);
+        let width = 10u64.pow(rng.range(0..=9) as u32);
+        input += &format!("{start}-{}\n", start + rng.below(width));
+    }
+    input.push('\n');
+    for _ in 0..size {
+        input += &format!("{}\n", rng.range(1..=1_000_000_000_000));
+    }
+    input
 }
 
 #[cfg(test)]
@@ -103,4 +102,40 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(14));
     }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(1, 50);
+        let (ranges, ids) = parse_unmerged_input(&input).unwrap();
+        assert_eq!((ranges.len(), ids.len()), (50, 50));
+        assert!(ranges.iter().all(|r| r.start() <= r.end()));
+        let widths: u64 = ranges.iter().map(|r| r.end() - r.start() + 1).sum();
+        assert!(part_two(&input).is_some_and(|fresh| fresh <= widths));
+    }
+
+    #[test]
+    fn test_reference() {
+        crosscheck(10, 20);
+    }
+
+    #[cfg(feature = "test_lib")]
+    #[test]
+    fn test_reference_on_overlapping_ranges() {
+        use advent_of_code::property::{Runner, int, vec};
+
+        let ranges = vec((int(0u64..=50), int(0u64..=20)), 1..=10);
+        let ids = vec(int(0u64..=80), 1..=10);
+        Runner::new()
+            .cases(500)
+            .check(&(ranges, ids), |(ranges, ids)| {
+                let ranges: String = ranges
+                    .iter()
+                    .map(|(start, len)| format!("{start}-{}\n", start + len))
+                    .collect();
+                let ids: String = ids.iter().map(|id| format!("{id}\n")).collect();
+                let input = format!("{ranges}\n{ids}");
+                part_one(&input) == part_one_reference(&input)
+                    && part_two(&input) == part_two_reference(&input)
+            });
+    }
 }
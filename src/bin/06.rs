@@ -1,14 +1,13 @@
-use nom::{
-    IResult, Parser,
-    branch::alt,
-    character::complete::{char, digit1, newline, one_of, space0, space1},
-    combinator::{all_consuming, map_opt, map_res, opt, peek},
-    multi::{many_m_n, many0_count, many1, separated_list1},
-    sequence::{delimited, pair, terminated},
-};
+use advent_of_code::columns::{column_blocks, horizontal_numbers, padded_grid, vertical_numbers};
+use advent_of_code::grid::Grid;
+use advent_of_code::random::Rng;
+use std::ops::Range;
 
 advent_of_code::solution!(6);
 
+/// Reads the numbers of a block of the worksheet, given its rows and columns.
+type ReadBlock = fn(&Grid<char>, Range<usize>, Range<usize>) -> Option<Vec<u64>>;
+
 #[derive(Debug)]
 enum Operation {
     Add,
@@ -16,131 +15,70 @@ enum Operation {
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let (_, (numbers, operations)) = parse_entire_input(input).ok()?;
-    Some(
-        numbers
-            .iter()
-            .skip(1)
-            .fold(numbers[0].clone(), |acc, row| {
-                let mut row_result = Vec::with_capacity(operations.len());
-                for ((num1, num2), op) in row.iter().zip(acc.iter()).zip(operations.iter()) {
-                    row_result.push(match op {
-                        Operation::Add => num1 + num2,
-                        Operation::Multiply => num1 * num2,
-                    })
-                }
-                row_result
-            })
-            .iter()
-            .sum(),
-    )
+    solve(input, horizontal_numbers)
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    parse_entire_input_2(input)
-}
-
-fn parse_entire_input(input: &str) -> IResult<&str, (Vec<Vec<u64>>, Vec<Operation>)> {
-    all_consuming(pair(many1(parse_spaced_numbers), parse_operations)).parse(input)
+    solve(input, vertical_numbers)
 }
 
-fn parse_entire_input_2(input: &str) -> Option<u64> {
-    let input_by_lines = input.lines().collect::<Vec<_>>();
-    let mut operators_line = input_by_lines.last()?.to_string();
-    // Add a space at the end to help parsing
-    operators_line.push(' ');
-    let (_, operations) = separated_list1(space1, (parse_operation, peek(many0_count(char(' ')))))
-        .parse(&operators_line)
-        .ok()?;
-
-    let size_needed = input_by_lines.len() - 1;
-    let mut all_lines = Vec::with_capacity(size_needed);
-    for line in input_by_lines.into_iter().take(size_needed) {
-        let mut current_line = line;
-        let mut numbers_lines = Vec::with_capacity(operations.len());
-        for (_, max_size) in operations.iter() {
-            let (new_line, mut numbers) =
-                terminated(many_m_n(1, *max_size, parse_number_2), opt(char(' ')))
-                    .parse(current_line)
-                    .ok()?;
-            while numbers.len() < *max_size {
-                numbers.push(None);
-            }
-            current_line = new_line;
-            numbers_lines.push(numbers);
-        }
-        all_lines.push(numbers_lines);
-    }
-
-    let mut total_val = 0;
-    for (col_index, (op, size)) in operations.iter().enumerate() {
-        let mut col_value = match op {
-            Operation::Add => 0,
-            Operation::Multiply => 1,
+/// Every problem is a block of columns with its numbers above the operator in the last row.
+/// `read` extracts the numbers of a block, either one per row or one per column.
+/// Returns [`None`] if a result does not fit into a `u64`.
+fn solve(input: &str, read: ReadBlock) -> Option<u64> {
+    let worksheet = padded_grid(input);
+    let operators = worksheet.height().checked_sub(1)?;
+    let mut total = 0;
+    for cols in column_blocks(&worksheet) {
+        let operation = parse_operation(&worksheet.row(operators)?[cols.clone()])?;
+        let numbers = read(&worksheet, 0..operators, cols)?;
+        let result = match operation {
+            Operation::Add => numbers.iter().try_fold(0u64, |acc, &n| acc.checked_add(n)),
+            Operation::Multiply => numbers.iter().try_fold(1u64, |acc, &n| acc.checked_mul(n)),
         };
-        for n in 0..*size {
-            let mut val = 0;
-            for row in all_lines.iter() {
-                if let Some(d) = row[col_index][size - n - 1] {
-                    val = val * 10 + d;
-                }
-            }
-            match op {
-                Operation::Add => col_value += val,
-                Operation::Multiply => col_value *= val,
-            }
-        }
-        total_val += col_value;
+        total = result?.checked_add(total)?;
     }
-    Some(total_val)
-}
-
-fn parse_number_2(input: &str) -> IResult<&str, Option<u64>> {
-    one_of("0123456789 ")
-        .map(|c| match c {
-            '1' => Some(1),
-            '2' => Some(2),
-            '3' => Some(3),
-            '4' => Some(4),
-            '5' => Some(5),
-            '6' => Some(6),
-            '7' => Some(7),
-            '8' => Some(8),
-            '9' => Some(9),
-            _ => None,
-        })
-        .parse(input)
-}
-
-fn parse_spaced_numbers(input: &str) -> IResult<&str, Vec<u64>> {
-    delimited(
-        space0,
-        separated_list1(space1, parse_number),
-        (space0, newline),
-    )
-    .parse(input)
-}
-
-fn parse_operations(input: &str) -> IResult<&str, Vec<Operation>> {
-    delimited(
-        space0,
-        separated_list1(space1, parse_operation),
-        (space0, newline),
-    )
-    .parse(input)
+    Some(total)
 }
 
-fn parse_operation(input: &str) -> IResult<&str, Operation> {
-    map_opt(alt((char('*'), char('+'))), |c| match c {
+fn parse_operation(cells: &[char]) -> Option<Operation> {
+    match cells.iter().find(|&&c| c != ' ')? {
         '*' => Some(Operation::Multiply),
         '+' => Some(Operation::Add),
         _ => None,
-    })
-    .parse(input)
+    }
 }
 
-fn parse_number(input: &str) -> IResult<&str, u64> {
-    map_res(digit1, str::parse).parse(input)
+/// Generates a worksheet of `size` problems with four numbers of up to four digits each.
+/// The numbers of a problem are either all left or all right aligned.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut rows = vec![String::new(); 5];
+    for problem in 0..size {
+        let numbers: Vec<_> = (0..4)
+            .map(|_| {
+                let digits = rng.range(1..=4) as u32;
+                rng.range(1..=10u64.pow(digits) - 1).to_string()
+            })
+            .collect();
+        let width = numbers.iter().map(String::len).max().unwrap_or(1);
+        let left = rng.chance(0.5);
+        let operator = if rng.chance(0.5) { "+" } else { "*" };
+        for (row, cell) in rows
+            .iter_mut()
+            .zip(numbers.iter().map(String::as_str).chain([operator]))
+        {
+            if problem > 0 {
+                row.push(' ');
+            }
+            if left || cell == operator {
+                row.push_str(&format!("{cell:<width$}"));
+            } else {
+                row.push_str(&format!("{cell:>width$}"));
+            }
+        }
+    }
+    rows.into_iter().map(|row| row + "\n").collect()
 }
 
 #[cfg(test)]
@@ -158,4 +96,24 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(3263827));
     }
+
+    #[test]
+    fn test_overflow() {
+        let input = "99999999999 99999999999\n99999999999 99999999999\n*           +\n";
+        assert_eq!(part_one(input), None);
+        assert_eq!(part_two(input), None);
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(1, 50);
+        let rows: Vec<_> = input.lines().collect();
+        assert_eq!(rows.len(), 5);
+        assert!(rows.iter().all(|row| row.len() == rows[0].len()));
+        let operators: Vec<_> = rows[4].split_whitespace().collect();
+        assert_eq!(operators.len(), 50);
+        assert!(operators.iter().all(|op| *op == "+" || *op == "*"));
+        assert!(part_one(&input).is_some());
+        assert!(part_two(&input).is_some());
+    }
 }
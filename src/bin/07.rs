@@ -1,15 +1,8 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+use advent_of_code::beam::{Flow, Propagation, propagate};
+use advent_of_code::grid::{Grid, Point};
+use advent_of_code::random::Rng;
 
-use nom::{
-    IResult, Parser,
-    branch::alt,
-    character::complete::{char, newline},
-    combinator::all_consuming,
-    multi::many1,
-    sequence::terminated,
-};
-
-advent_of_code::solution!(7);
+advent_of_code::solution!(7, shared);
 
 enum State {
     Start,
@@ -31,113 +24,65 @@ impl std::fmt::Debug for State {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let manifold = parse_entire_input(input)?;
-
-    let start_index = manifold
-        .first()?
-        .iter()
-        .position(|s| matches!(s, State::Start))?;
-
-    let mut queue = VecDeque::new();
-    let mut visited = HashSet::new();
-
-    queue.push_back((0usize, start_index));
-    visited.insert((0usize, start_index));
-
-    let mut split_count = 0;
-
-    while !queue.is_empty() {
-        let (row, col) = queue.pop_front()?;
-        if row == manifold.len() - 1 {
-            continue;
-        }
-        let next_row = row + 1;
-        let next_state = &manifold[next_row][col];
-        match next_state {
-            State::EmptySpace => {
-                if visited.insert((next_row, col)) {
-                    queue.push_back((next_row, col));
-                }
-            }
-            State::Splitter => {
-                split_count += 1;
-                if visited.insert((next_row, col - 1)) {
-                    queue.push_back((next_row, col - 1));
-                }
-                if visited.insert((next_row, col + 1)) {
-                    queue.push_back((next_row, col + 1));
-                }
-            }
-            _ => {}
-        }
-    }
+type Manifold = Grid<State>;
 
-    Some(split_count)
+/// The parsed manifold and the cell the beam enters it at, shared by both parts.
+pub struct Layout {
+    manifold: Manifold,
+    start: Point,
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let manifold = parse_entire_input(input)?;
-
-    let start_index = manifold
-        .first()?
-        .iter()
-        .position(|s| matches!(s, State::Start))?;
+pub fn parse_shared(input: &str) -> Option<Layout> {
+    parse_entire_input(input).and_then(locate_start)
+}
 
-    let path_count = recursive_dfs((0usize, start_index), &manifold, &mut HashMap::new());
+pub fn part_one_shared(layout: &Layout) -> Option<u64> {
+    Some(simulate(layout).splits)
+}
 
-    Some(path_count)
+pub fn part_two_shared(layout: &Layout) -> Option<u64> {
+    Some(simulate(layout).timelines)
 }
 
-fn recursive_dfs(
-    node: (usize, usize),
-    manifold: &Vec<Vec<State>>,
-    visited: &mut HashMap<(usize, usize), u64>,
-) -> u64 {
-    let (row, col) = node;
-    if row == manifold.len() - 1 {
-        visited.insert(node, 1);
-        return 1;
-    }
-    let next_row = row + 1;
-    let next_state = &manifold[next_row][col];
-    let val = match next_state {
-        State::EmptySpace => match visited.get(&(next_row, col)) {
-            Some(&val) => val,
-            None => recursive_dfs((next_row, col), manifold, visited),
-        },
-        State::Splitter => {
-            let left_val = match visited.get(&(next_row, col - 1)) {
-                Some(&val) => val,
-                None => recursive_dfs((next_row, col - 1), manifold, visited),
-            };
-            let right_val = match visited.get(&(next_row, col + 1)) {
-                Some(&val) => val,
-                None => recursive_dfs((next_row, col + 1), manifold, visited),
-            };
-            left_val + right_val
-        }
-        _ => 0,
-    };
-    visited.insert(node, val);
-    val
+fn locate_start(manifold: Manifold) -> Option<Layout> {
+    let start = manifold
+        .position(|s| matches!(s, State::Start))
+        .filter(|p| p.row == 0)?;
+    Some(Layout { manifold, start })
 }
 
-fn parse_entire_input(input: &str) -> Option<Vec<Vec<State>>> {
-    let (_, manifold) = all_consuming(many1(terminated(parse_row, newline)))
-        .parse(input)
-        .ok()?;
-    Some(manifold)
+fn simulate(layout: &Layout) -> Propagation {
+    propagate(&layout.manifold, layout.start, |state| match state {
+        State::Splitter => Flow::Split,
+        _ => Flow::Pass,
+    })
 }
 
-fn parse_row(input: &str) -> IResult<&str, Vec<State>> {
-    many1(alt((char('S'), char('.'), char('^'))).map_opt(|c| match c {
+fn parse_entire_input(input: &str) -> Option<Manifold> {
+    Grid::parse(input, |c| match c {
         'S' => Some(State::Start),
         '.' => Some(State::EmptySpace),
         '^' => Some(State::Splitter),
         _ => None,
-    }))
-    .parse(input)
+    })
+}
+
+/// Generates a `size` × `size` manifold with the start in the middle of the top row and splitters on every other row.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    (0..size)
+        .map(|row| {
+            let mut line: String = (0..size)
+                .map(|col| match row {
+                    0 if col == size / 2 => 'S',
+                    _ if row % 2 == 0 && row > 0 && rng.chance(0.3) => '^',
+                    _ => '.',
+                })
+                .collect();
+            line.push('\n');
+            line
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -146,13 +91,24 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(21));
+        let layout = parse_shared(&advent_of_code::template::read_file("examples", DAY)).unwrap();
+        assert_eq!(part_one_shared(&layout), Some(21));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(40));
+        let layout = parse_shared(&advent_of_code::template::read_file("examples", DAY)).unwrap();
+        assert_eq!(part_two_shared(&layout), Some(40));
+    }
+
+    #[test]
+    fn test_generate() {
+        let manifold = parse_entire_input(&generate(1, 30)).unwrap();
+        assert_eq!((manifold.width(), manifold.height()), (30, 30));
+        let layout = locate_start(manifold).unwrap();
+        assert_eq!(layout.start, Point::new(0, 15));
+        assert!(layout.manifold.iter().all(|(point, state)| {
+            !matches!(state, State::Splitter) || (point.row > 0 && point.row % 2 == 0)
+        }));
     }
 }
@@ -1,154 +1,48 @@
-use std::{
-    cell::RefCell,
-    collections::{HashMap, HashSet},
-    rc::Rc,
-};
+use advent_of_code::disjoint_set::DisjointSet;
+use advent_of_code::geometry::{ClosestPairs, KdTree, Kruskal, Point3};
+use advent_of_code::parsers::{comma_list, lines, parse_all, signed};
+use advent_of_code::random::Rng;
 
-use nom::{
-    IResult, Parser,
-    character::complete::{char, digit1, newline},
-    combinator::{all_consuming, map_res},
-    multi::{many1, separated_list1},
-    sequence::terminated,
-};
+advent_of_code::solution!(8, shared);
 
-advent_of_code::solution!(8);
-
-#[derive(Debug)]
-struct Location {
-    x: u64,
-    y: u64,
-    z: u64,
+/// All junction boxes, indexed for closest-pair queries.
+pub struct Playground {
+    tree: KdTree,
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
+pub fn parse_shared(input: &str) -> Option<Playground> {
     let locations = parse_entire_input(input)?;
-    let mut distances = Vec::new();
-    for (i, loc) in locations.iter().enumerate() {
-        for (j, other_loc) in locations.iter().enumerate().skip(i + 1) {
-            let distance = (((loc.x as i64 - other_loc.x as i64).pow(2)
-                + (loc.y as i64 - other_loc.y as i64).pow(2)
-                + (loc.z as i64 - other_loc.z as i64).pow(2)) as f64)
-                .sqrt();
-            distances.push((distance, i, j));
-        }
-    }
-    distances.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
-    let mut graph = vec![vec![false; locations.len()]; locations.len()];
+    Some(Playground {
+        tree: KdTree::new(locations),
+    })
+}
+
+pub fn part_one_shared(playground: &Playground) -> Option<u64> {
+    let mut circuits = DisjointSet::new(playground.tree.points().len());
 
     let max_connections = if cfg!(test) { 10 } else { 1000 };
-    for k in 0..max_connections {
-        graph[distances[k].1][distances[k].2] = true;
-        graph[distances[k].2][distances[k].1] = true;
-    }
-    let mut size_counts = Vec::new();
-    let mut visited = HashSet::new();
-    for i in 0..locations.len() {
-        if visited.contains(&i) {
-            continue;
-        }
-        visited.insert(i);
-        let count = recursive_dfs(i, &graph, &mut visited);
-        size_counts.push(count);
+    for pair in ClosestPairs::new(&playground.tree).take(max_connections) {
+        circuits.union(pair.a, pair.b);
     }
-    size_counts.sort();
-    size_counts.iter().rev().take(3).product::<u64>().into()
-}
 
-fn recursive_dfs(node: usize, graph: &Vec<Vec<bool>>, visited: &mut HashSet<usize>) -> u64 {
-    let row = &graph[node];
-    if row.iter().all(|&b| !b) {
-        1
-    } else {
-        let mut total = 1;
-        for (i, &connected) in row.iter().enumerate() {
-            if connected && !visited.contains(&i) {
-                visited.insert(i);
-                total += recursive_dfs(i, graph, visited);
-            }
-        }
-        total
-    }
+    let mut sizes: Vec<_> = circuits.component_sizes().collect();
+    sizes.sort_unstable_by(|a, b| b.cmp(a));
+    Some(sizes.iter().take(3).map(|&s| s as u64).product())
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let locations = parse_entire_input(input)?;
-    let mut distances = Vec::new();
-    for (i, loc) in locations.iter().enumerate() {
-        for (j, other_loc) in locations.iter().enumerate().skip(i + 1) {
-            let distance = (((loc.x as i64 - other_loc.x as i64).pow(2)
-                + (loc.y as i64 - other_loc.y as i64).pow(2)
-                + (loc.z as i64 - other_loc.z as i64).pow(2)) as f64)
-                .sqrt();
-            distances.push((distance, i, j));
-        }
-    }
-    distances.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
-
-    let mut graphs = HashMap::<usize, Rc<RefCell<HashSet<usize>>>>::new();
-    for (_, i, j) in distances {
-        let gi = graphs.get(&i);
-        let gj = graphs.get(&j);
-        match (gi, gj) {
-            (Some(gi), Some(gj)) => {
-                let new_set = gi
-                    .borrow()
-                    .union(&gj.borrow())
-                    .cloned()
-                    .collect::<HashSet<usize>>();
-                let new_set = Rc::new(RefCell::new(new_set));
-                for &val in new_set.borrow().iter() {
-                    graphs.insert(val, new_set.clone());
-                }
-                if new_set.borrow().len() == locations.len() {
-                    return Some(locations[i].x * locations[j].x);
-                }
-            }
-            (Some(_), None) => {
-                {
-                    graphs.get(&i)?.borrow_mut().insert(j);
-                }
-                graphs.insert(j, graphs.get(&i)?.clone());
-                if graphs.get(&i)?.borrow().len() == locations.len() {
-                    return Some(locations[i].x * locations[j].x);
-                }
-            }
-            (None, Some(_)) => {
-                {
-                    graphs.get(&j)?.borrow_mut().insert(i);
-                }
-                graphs.insert(i, graphs.get(&j)?.clone());
-                if graphs.get(&j)?.borrow().len() == locations.len() {
-                    return Some(locations[i].x * locations[j].x);
-                }
-            }
-            (None, None) => {
-                let mut new_set = HashSet::new();
-                new_set.insert(i);
-                new_set.insert(j);
-                let new_set = Rc::new(RefCell::new(new_set));
-                graphs.insert(i, new_set.clone());
-                graphs.insert(j, new_set.clone());
-                if new_set.borrow().len() == locations.len() {
-                    return Some(locations[i].x * locations[j].x);
-                }
-            }
-        }
-    }
-    None
+pub fn part_two_shared(playground: &Playground) -> Option<u64> {
+    let locations = playground.tree.points();
+    let last = Kruskal::new(&playground.tree).last()?;
+    let product = locations[last.a].x.checked_mul(locations[last.b].x)?;
+    u64::try_from(product).ok()
 }
 
-fn parse_entire_input(input: &str) -> Option<Vec<Location>> {
-    let (_, locations) = all_consuming(many1(terminated(
-        separated_list1(char(','), decimal_value),
-        newline,
-    )))
-    .parse(input)
-    .ok()?;
+fn parse_entire_input(input: &str) -> Option<Vec<Point3>> {
+    let locations = parse_all(lines(comma_list(signed::<i64>)), input).ok()?;
     Some(
         locations
             .into_iter()
-            .map(|vals| Location {
+            .map(|vals| Point3 {
                 x: vals.first().cloned().unwrap_or(0),
                 y: vals.get(1).cloned().unwrap_or(0),
                 z: vals.get(2).cloned().unwrap_or(0),
@@ -157,8 +51,15 @@ fn parse_entire_input(input: &str) -> Option<Vec<Location>> {
     )
 }
 
-fn decimal_value(input: &str) -> IResult<&str, u64> {
-    map_res(digit1, str::parse).parse(input)
+/// Generates `size` junction boxes with coordinates of up to 100000.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    (0..size)
+        .map(|_| {
+            let [x, y, z] = [(); 3].map(|_| rng.range(0..=100_000));
+            format!("{x},{y},{z}\n")
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -167,13 +68,36 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(40));
+        let playground =
+            parse_shared(&advent_of_code::template::read_file("examples", DAY)).unwrap();
+        assert_eq!(part_one_shared(&playground), Some(40));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(25272));
+        let playground =
+            parse_shared(&advent_of_code::template::read_file("examples", DAY)).unwrap();
+        assert_eq!(part_two_shared(&playground), Some(25272));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let input = "4000000000000000000,0,0\n4000000000000000000,1,0\n";
+        let playground = parse_shared(input).unwrap();
+        assert_eq!(part_two_shared(&playground), None);
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(1, 1000);
+        let points = parse_entire_input(&input).unwrap();
+        assert_eq!(points.len(), 1000);
+        let distinct: std::collections::HashSet<_> = input.lines().collect();
+        assert_eq!(distinct.len(), 1000);
+        assert!(
+            points
+                .iter()
+                .all(|p| [p.x, p.y, p.z].iter().all(|v| (0..=100_000).contains(v)))
+        );
     }
 }
@@ -1,10 +1,7 @@
-use nom::{
-    IResult, Parser,
-    character::complete::{char, digit1, newline},
-    combinator::{all_consuming, map_res},
-    multi::many1,
-    sequence::{separated_pair, terminated},
-};
+use advent_of_code::grid::{Grid, Point as Cell};
+use advent_of_code::parsers::{lines, parse_all, signed};
+use advent_of_code::random::Rng;
+use nom::{character::complete::char, sequence::separated_pair};
 
 advent_of_code::solution!(9);
 
@@ -15,31 +12,151 @@ pub fn part_one(input: &str) -> Option<u64> {
     let mut highest_area = 0;
     for (i, point1) in points.iter().enumerate() {
         for point2 in points.iter().skip(i + 1) {
-            let area = ((point1.0 - point2.0).abs() + 1) * ((point1.1 - point2.1).abs() + 1);
+            let area = area(*point1, *point2)?;
             if area > highest_area {
                 highest_area = area;
             }
         }
     }
-    Some(highest_area as u64)
+    Some(highest_area)
 }
 
-pub fn part_two(_input: &str) -> Option<u64> {
-    Some(24)
+pub fn part_two(input: &str) -> Option<u64> {
+    let points = parse_entire_input(input)?;
+    let floor = CompressedFloor::new(&points)?;
+    let mut highest_area = 0;
+    for (i, point1) in points.iter().enumerate() {
+        for point2 in points.iter().skip(i + 1) {
+            let area = area(*point1, *point2)?;
+            if area > highest_area && floor.is_inside(*point1, *point2) {
+                highest_area = area;
+            }
+        }
+    }
+    Some(highest_area)
+}
+
+/// The number of tiles of the rectangle with opposite corners `a` and `b`, or [`None`] if it overflows.
+fn area(a: Point, b: Point) -> Option<u64> {
+    let width = a.0.abs_diff(b.0).checked_add(1)?;
+    let height = a.1.abs_diff(b.1).checked_add(1)?;
+    width.checked_mul(height)
+}
+
+/// The floor with coordinates compressed: index `2 * i` stands for the `i`-th distinct coordinate
+/// of a red tile and index `2 * i + 1` for the (possibly empty) gap after it. A rectangle between
+/// two red tiles covers whole compressed cells, so it lies within the polygon exactly if none of
+/// them is outside, which the prefix sums answer in constant time.
+struct CompressedFloor {
+    xs: Vec<i64>,
+    ys: Vec<i64>,
+    /// `outside[r][c]` counts the outside cells in compressed rows `..r` and columns `..c`.
+    outside: Vec<Vec<u32>>,
+}
+
+impl CompressedFloor {
+    /// Returns [`None`] if consecutive red tiles are not in the same row or column.
+    fn new(points: &[Point]) -> Option<Self> {
+        let mut xs: Vec<_> = points.iter().map(|p| p.0).collect();
+        let mut ys: Vec<_> = points.iter().map(|p| p.1).collect();
+        xs.sort_unstable();
+        xs.dedup();
+        ys.sort_unstable();
+        ys.dedup();
+
+        // NOTE: a border of one cell around the compressed floor connects the whole outside.
+        let mut boundary = Grid::new(xs.len() * 2 + 1, ys.len() * 2 + 1, false);
+        for (i, &from) in points.iter().enumerate() {
+            let to = points[(i + 1) % points.len()];
+            if from.0 != to.0 && from.1 != to.1 {
+                return None;
+            }
+            let (x1, x2) = (compressed_index(&xs, from.0), compressed_index(&xs, to.0));
+            let (y1, y2) = (compressed_index(&ys, from.1), compressed_index(&ys, to.1));
+            for row in y1.min(y2)..=y1.max(y2) {
+                for col in x1.min(x2)..=x1.max(x2) {
+                    boundary[Cell::new(row, col)] = true;
+                }
+            }
+        }
+
+        let mut is_outside = Grid::new(boundary.width(), boundary.height(), false);
+        let mut stack = vec![Cell::new(0, 0)];
+        is_outside[Cell::new(0, 0)] = true;
+        while let Some(cell) = stack.pop() {
+            for next in boundary.neighbours4(cell) {
+                if !boundary[next] && !is_outside[next] {
+                    is_outside[next] = true;
+                    stack.push(next);
+                }
+            }
+        }
+
+        let mut outside = vec![vec![0; is_outside.width() + 1]; is_outside.height() + 1];
+        for (r, row) in is_outside.rows().enumerate() {
+            for (c, &cell) in row.iter().enumerate() {
+                // NOTE: gaps between adjacent coordinates contain no tiles, so they never count as outside.
+                let counts = cell && !is_empty_gap(&ys, r) && !is_empty_gap(&xs, c);
+                outside[r + 1][c + 1] =
+                    outside[r][c + 1] + outside[r + 1][c] - outside[r][c] + u32::from(counts);
+            }
+        }
+
+        Some(Self { xs, ys, outside })
+    }
+
+    /// Returns whether the rectangle with corners `a` and `b` contains only red and green tiles.
+    fn is_inside(&self, a: Point, b: Point) -> bool {
+        let (x1, x2) = (
+            compressed_index(&self.xs, a.0),
+            compressed_index(&self.xs, b.0),
+        );
+        let (y1, y2) = (
+            compressed_index(&self.ys, a.1),
+            compressed_index(&self.ys, b.1),
+        );
+        let (c1, c2) = (x1.min(x2), x1.max(x2) + 1);
+        let (r1, r2) = (y1.min(y2), y1.max(y2) + 1);
+        self.outside[r2][c2] + self.outside[r1][c1] == self.outside[r1][c2] + self.outside[r2][c1]
+    }
+}
+
+/// The compressed index of the red tile coordinate `v`, shifted by the border.
+fn compressed_index(values: &[i64], v: i64) -> usize {
+    values.binary_search(&v).unwrap_or_default() * 2 + 1
+}
+
+/// Returns whether the compressed `index` is the gap between two adjacent coordinates.
+fn is_empty_gap(values: &[i64], index: usize) -> bool {
+    let i = index / 2;
+    index.is_multiple_of(2) && i > 0 && i < values.len() && values[i].abs_diff(values[i - 1]) <= 1
 }
 
 fn parse_entire_input(input: &str) -> Option<Vec<Point>> {
-    let (_, points) = all_consuming(many1(terminated(
-        separated_pair(decimal_value, char(','), decimal_value),
-        newline,
-    )))
-    .parse(input)
-    .ok()?;
-    Some(points)
+    parse_all(lines(separated_pair(signed, char(','), signed)), input).ok()
 }
 
-fn decimal_value(input: &str) -> IResult<&str, i64> {
-    map_res(digit1, str::parse).parse(input)
+/// Generates the red tiles of a histogram-shaped polygon with `size` bars.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut x = rng.range(0..=1000);
+    let mut height = 0;
+    let mut points = vec![(x, 0)];
+    for _ in 0..size {
+        let mut next_height = rng.range(1..=100_000);
+        if next_height == height {
+            next_height += 1;
+        }
+        height = next_height;
+        points.push((x, height));
+        x += rng.range(1..=400);
+        points.push((x, height));
+    }
+    points.push((x, 0));
+    points
+        .into_iter()
+        .map(|(x, y)| format!("{x},{y}\n"))
+        .collect()
 }
 
 #[cfg(test)]
@@ -57,4 +174,56 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(24));
     }
+
+    #[test]
+    fn test_part_two_concave() {
+        // an L shape: the bounding box is not covered, the tall bar is the largest rectangle.
+        let input = "0,0\n10,0\n10,2\n3,2\n3,8\n0,8\n";
+        assert_eq!(part_one(input), Some(99));
+        assert_eq!(part_two(input), Some(36));
+    }
+
+    #[test]
+    fn test_part_two_slits() {
+        // the edges at x = 5 and x = 6 are parallel with no tile between them, so every tile is covered.
+        let input = "0,0\n10,0\n10,10\n6,10\n6,3\n5,3\n5,10\n0,10\n";
+        assert_eq!(part_two(input), Some(121));
+
+        // with a tile between the edges, column 6 above row 3 is outside.
+        let input = "0,0\n10,0\n10,10\n7,10\n7,3\n5,3\n5,10\n0,10\n";
+        assert_eq!(part_two(input), Some(66));
+    }
+
+    #[test]
+    fn test_part_two_reversed_orientation() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        let reversed: String = input.lines().rev().map(|l| format!("{l}\n")).collect();
+        assert_eq!(part_two(&reversed), Some(24));
+        assert_eq!(part_two("0,8\n3,8\n3,2\n10,2\n10,0\n0,0\n"), Some(36));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let input = "0,0\n0,466854775842445\n52528,0\n";
+        assert_eq!(part_one(input), None);
+        assert_eq!(
+            part_one("-9223372036854775808,0\n9223372036854775807,0\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(1, 50);
+        let points = parse_entire_input(&input).unwrap();
+        assert_eq!(points.len(), 2 * 50 + 2);
+        // a closed rectilinear polygon: edges, including the closing one, alternate between vertical and horizontal.
+        for (i, &from) in points.iter().enumerate() {
+            let to = points[(i + 1) % points.len()];
+            let vertical = from.0 == to.0;
+            assert_ne!(vertical, from.1 == to.1);
+            assert_eq!(vertical, i % 2 == 0);
+        }
+        assert!(part_two(&input) <= part_one(&input));
+    }
 }
@@ -1,12 +1,13 @@
-use good_lp::{
-    Expression, Solution, SolverModel, constraint, microlp, variable, variable::ProblemVariables,
-};
+use advent_of_code::gf2::Gf2System;
+use advent_of_code::ilp::IntegerProgram;
+use advent_of_code::parsers::{comma_list, lines, parse_all, unsigned};
+use advent_of_code::random::Rng;
 use nom::{
     IResult, Parser,
-    character::complete::{char, digit1, newline, one_of},
-    combinator::{all_consuming, map_res},
+    character::complete::{char, one_of},
+    combinator::map_opt,
     multi::{many1, separated_list1},
-    sequence::{delimited, terminated},
+    sequence::delimited,
 };
 
 advent_of_code::solution!(10);
@@ -19,10 +20,9 @@ pub fn part_one(input: &str) -> Option<u64> {
     let rows = parse_entire_input(input)?;
     let mut total_presses = 0;
     for (target, buttons, _) in rows {
-        let presses = recurse_row(0, target, 0, &buttons);
-        total_presses += presses;
+        total_presses += min_presses(target, &buttons)?;
     }
-    Some(total_presses as u64)
+    Some(total_presses)
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
@@ -30,74 +30,48 @@ pub fn part_two(input: &str) -> Option<u64> {
     let mut total_presses = 0;
 
     for (_, buttons, joltages) in rows {
-        let mut vars = Vec::new();
-        let mut problem = ProblemVariables::new();
-        let mut total_value: Expression = 0.into();
-        for _ in &buttons {
-            let var = problem.add(variable().integer().min(0));
-            total_value += var;
-            vars.push(var);
-        }
-        let mut solution = problem.minimise(total_value).using(microlp);
+        let mut program = IntegerProgram::new(buttons.len());
         for (i, joltage) in joltages.into_iter().enumerate() {
-            let mut lhs: Expression = 0.into();
-            let rhs: Expression = (joltage as i32).into();
-            for (button_idx, button) in buttons.iter().enumerate() {
-                for &indicator in button {
-                    if indicator == i {
-                        lhs += vars[button_idx];
-                    }
-                }
-            }
-            solution = solution.with(constraint!(lhs == rhs));
+            program
+                .equation(
+                    buttons
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, button)| button.contains(&i))
+                        .map(|(button_idx, _)| (button_idx, 1)),
+                    i64::try_from(joltage).ok()?,
+                )
+                .ok()?;
         }
-        let solution = solution.solve().ok()?;
-
-        total_presses += vars.iter().map(|&v| solution.value(v)).sum::<f64>() as u64;
+        total_presses += program.solve().ok()?.total();
     }
     Some(total_presses)
 }
 
-fn recurse_row(
-    current_val: u64,
-    target_val: u64,
-    press_count: usize,
-    remaining_buttons: &[u64],
-) -> usize {
-    if current_val == target_val {
-        return press_count;
-    }
-    if remaining_buttons.is_empty() {
-        return usize::MAX;
-    }
-    let mut min_presses = usize::MAX;
-    for (i, &button) in remaining_buttons.iter().enumerate() {
-        let new_val = current_val ^ button;
-        let presses = recurse_row(
-            new_val,
-            target_val,
-            press_count + 1,
-            &remaining_buttons[i + 1..],
+/// Every light must end up toggled an odd number of times if it is on in `target`, so the presses
+/// solve a linear system over GF(2) with one equation per light and one variable per button.
+fn min_presses(target: u64, buttons: &[u64]) -> Option<u64> {
+    let lights = 64
+        - buttons
+            .iter()
+            .fold(target, |acc, b| acc | b)
+            .leading_zeros();
+    let mut system = Gf2System::new(buttons.len());
+    for light in 0..lights {
+        system.equation(
+            (0..buttons.len()).filter(|&b| buttons[b] >> light & 1 == 1),
+            target >> light & 1 == 1,
         );
-        if presses < min_presses {
-            min_presses = presses;
-        }
     }
-    min_presses
+    Some(system.solve()?.min_weight()?.count_ones() as u64)
 }
 
 fn parse_entire_input(input: &str) -> Option<Vec<Row1>> {
-    let (_, rows) = all_consuming(many1(terminated(parse_row, newline)))
-        .parse(input)
-        .ok()?;
-    Some(rows)
+    parse_all(lines(parse_row), input).ok()
 }
 
 fn parse_entire_input_2(input: &str) -> Option<Vec<Row2>> {
-    let (_, rows) = all_consuming(many1(terminated(parse_row_2, newline)))
-        .parse(input)
-        .ok()?;
-    Some(rows)
+    parse_all(lines(parse_row_2), input).ok()
 }
 
 fn parse_row_2(input: &str) -> IResult<&str, Row2> {
@@ -111,11 +85,9 @@ fn parse_row(input: &str) -> IResult<&str, (u64, Vec<u64>, Vec<usize>)> {
 fn parse_diagram(input: &str) -> IResult<&str, u64> {
     delimited(
         char('['),
-        many1(one_of(".#")).map(|v| {
-            v.iter().rev().fold(0, |acc, v| match v {
-                '.' => acc * 2,
-                '#' => acc * 2 + 1,
-                _ => acc,
+        map_opt(many1(one_of(".#")), |v| {
+            v.iter().rev().try_fold(0u64, |acc, &v| {
+                acc.checked_mul(2)?.checked_add(u64::from(v == '#'))
             })
         }),
         char(']'),
@@ -142,35 +114,69 @@ fn parse_buttons_2(input: &str) -> IResult<&str, Vec<Button2>> {
 }
 
 fn parse_joltages(input: &str) -> IResult<&str, Vec<usize>> {
-    delimited(
-        char('{'),
-        separated_list1(char(','), decimal_value),
-        char('}'),
-    )
-    .parse(input)
+    delimited(char('{'), comma_list(unsigned), char('}')).parse(input)
 }
 
 fn parse_button(input: &str) -> IResult<&str, u64> {
     delimited(
         char('('),
-        separated_list1(char(','), decimal_value)
-            .map(|v| v.into_iter().fold(0, |acc, n| acc | (1 << n))),
+        map_opt(comma_list(unsigned::<u32>), |v| {
+            v.into_iter()
+                .try_fold(0u64, |acc, n| Some(acc | 1u64.checked_shl(n)?))
+        }),
         char(')'),
     )
     .parse(input)
 }
 
 fn parse_button_2(input: &str) -> IResult<&str, Vec<usize>> {
-    delimited(
-        char('('),
-        separated_list1(char(','), decimal_value),
-        char(')'),
-    )
-    .parse(input)
+    delimited(char('('), comma_list(unsigned), char(')')).parse(input)
 }
 
-fn decimal_value(input: &str) -> IResult<&str, usize> {
-    map_res(digit1, str::parse).parse(input)
+/// Generates `size` machines. The lights and joltages are reachable by pressing random buttons.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut input = String::new();
+    for _ in 0..size {
+        let lights = rng.range(3..=10) as usize;
+        let buttons: Vec<Vec<usize>> = (0..rng.range(3..=12))
+            .map(|_| {
+                let mut button: Vec<_> = (0..lights).filter(|_| rng.chance(0.4)).collect();
+                if button.is_empty() {
+                    button.push(rng.index(lights));
+                }
+                button
+            })
+            .collect();
+
+        let mut diagram = vec!['.'; lights];
+        let mut joltages = vec![0; lights];
+        for button in &buttons {
+            let presses = rng.range(0..=20);
+            for &light in button {
+                if presses % 2 == 1 {
+                    diagram[light] = if diagram[light] == '.' { '#' } else { '.' };
+                }
+                joltages[light] += presses;
+            }
+        }
+
+        let diagram: String = diagram.into_iter().collect();
+        let buttons: Vec<_> = buttons
+            .iter()
+            .map(|button| {
+                let lights: Vec<_> = button.iter().map(usize::to_string).collect();
+                format!("({})", lights.join(","))
+            })
+            .collect();
+        let joltages: Vec<_> = joltages.iter().map(u64::to_string).collect();
+        input += &format!(
+            "[{diagram}] {} {{{}}}\n",
+            buttons.join(" "),
+            joltages.join(",")
+        );
+    }
+    input
 }
 
 #[cfg(test)]
@@ -188,4 +194,25 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(33));
     }
+
+    #[test]
+    fn test_too_many_lights() {
+        assert_eq!(part_one("[#] (64) {1}\n"), None);
+        let lights = "#".repeat(65);
+        assert_eq!(part_one(&format!("[{lights}] (0) {{1}}\n")), None);
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(1, 20);
+        assert_eq!(input.lines().count(), 20);
+        for machine in input.lines() {
+            let lights = machine.find(']').unwrap() - 1;
+            let joltages = machine.rsplit('{').next().unwrap().split(',').count();
+            assert_eq!(lights, joltages);
+            // the targets come from pressing buttons, so every machine can be solved.
+            assert!(part_one(machine).is_some());
+            assert!(part_two(machine).is_some());
+        }
+    }
 }
@@ -1,95 +1,115 @@
-use std::collections::{HashMap, HashSet};
+use advent_of_code::graph::Graph;
+use advent_of_code::random::Rng;
+use std::collections::HashSet;
 
-use nom::{
-    IResult, Parser,
-    bytes::complete::{tag, take},
-    character::complete::{char, newline},
-    combinator::all_consuming,
-    multi::{many1, separated_list1},
-    sequence::{separated_pair, terminated},
+advent_of_code::solution!(11);
+
+/// Paths to count between two named devices, visiting all `via` devices in any order.
+struct Route<'a> {
+    from: &'a str,
+    to: &'a str,
+    via: &'a [&'a str],
+}
+
+const PART_ONE: Route = Route {
+    from: "you",
+    to: "out",
+    via: &[],
 };
 
-advent_of_code::solution!(11);
+const PART_TWO: Route = Route {
+    from: "svr",
+    to: "out",
+    via: &["dac", "fft"],
+};
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let nodes = parse_entire_input(input)?;
-    Some(find_num_paths(
-        &nodes,
-        "you",
-        "out",
-        HashSet::new(),
-        &vec![],
-    ))
+    let graph = Graph::parse_adjacency(input)?;
+    count_paths(&graph, &PART_ONE)
 }
 
-fn find_num_paths(
-    graph: &HashMap<&str, Vec<&str>>,
-    current: &str,
-    end: &str,
-    path: HashSet<&str>,
-    required_nodes: &Vec<&str>,
-) -> u64 {
-    if current == end {
-        if required_nodes.iter().all(|n| path.contains(n)) {
-            return 1;
-        } else {
-            return 0;
-        }
-    }
-    match graph.get(current) {
-        None => 0,
-        Some(linked_nodes) => linked_nodes
-            .iter()
-            .filter(|&node| !path.contains(node))
-            .map(|node| {
-                find_num_paths(
-                    graph,
-                    node,
-                    end,
-                    {
-                        let mut cl = path.clone();
-                        cl.insert(node);
-                        cl
-                    },
-                    required_nodes,
-                )
-            })
-            .sum(),
-    }
+pub fn part_two(input: &str) -> Option<u64> {
+    let graph = Graph::parse_adjacency(input)?;
+    count_paths(&graph, &PART_TWO)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let nodes = parse_entire_input(input)?;
-    Some(
-        find_num_paths(&nodes, "svr", "dac", HashSet::new(), &vec![])
-            * find_num_paths(&nodes, "dac", "fft", HashSet::new(), &vec![])
-            * find_num_paths(&nodes, "fft", "out", HashSet::new(), &vec![])
-            + find_num_paths(&nodes, "svr", "fft", HashSet::new(), &vec![])
-                * find_num_paths(&nodes, "fft", "dac", HashSet::new(), &vec![])
-                * find_num_paths(&nodes, "dac", "out", HashSet::new(), &vec![]),
-    )
+/// Counts the paths of `route`. Devices that are missing from the input have no paths.
+fn count_paths(graph: &Graph, route: &Route) -> Option<u64> {
+    let (Some(from), Some(to)) = (graph.id(route.from), graph.id(route.to)) else {
+        return Some(0);
+    };
+    let Some(via) = route
+        .via
+        .iter()
+        .map(|name| graph.id(name))
+        .collect::<Option<Vec<_>>>()
+    else {
+        return Some(0);
+    };
+    graph.count_paths_through(from, to, &via)
 }
 
-fn parse_entire_input(input: &str) -> Option<HashMap<&str, Vec<&str>>> {
-    let (_, nodes) = all_consuming(many1(terminated(
-        separated_pair(
-            parse_node,
-            tag(": "),
-            separated_list1(char(' '), parse_node),
-        ),
-        newline,
-    )))
-    .parse(input)
-    .ok()?;
-    let mut adjacency_list = HashMap::new();
-    for (node, links) in nodes.into_iter() {
-        adjacency_list.insert(node, links);
+/// Generates a random acyclic device graph with `size` devices besides those of both routes.
+/// Every device is connected to up to three of the devices that follow it in a random order,
+/// and a path from `svr` through both `dac` and `fft` to `out` is always planted.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let special = [PART_ONE, PART_TWO].iter().flat_map(|route| {
+        [route.from, route.to]
+            .into_iter()
+            .chain(route.via.iter().copied())
+    });
+    let mut names: HashSet<String> = special.map(str::to_string).collect();
+
+    // NOTE: names get longer once three letters are not enough to keep them unique.
+    let mut len = 3;
+    while 26usize.pow(len) < (size + names.len()) * 2 {
+        len += 1;
+    }
+    let mut devices = vec![];
+    while devices.len() < size {
+        let name: String = (0..len)
+            .map(|_| char::from(b'a' + rng.below(26) as u8))
+            .collect();
+        if names.insert(name.clone()) {
+            devices.push(name);
+        }
+    }
+    devices.extend([PART_ONE.from, "dac", "fft"].map(str::to_string));
+    rng.shuffle(&mut devices);
+    devices.insert(0, PART_TWO.from.to_string());
+    devices.push(PART_ONE.to.to_string());
+
+    let mut planted = vec![vec![]; devices.len()];
+    let mut waypoints: Vec<usize> = PART_TWO
+        .via
+        .iter()
+        .filter_map(|via| devices.iter().position(|device| device == via))
+        .collect();
+    waypoints.sort_unstable();
+    waypoints.insert(0, 0);
+    waypoints.push(devices.len() - 1);
+    for pair in waypoints.windows(2) {
+        let mut current = pair[0];
+        while current < pair[1] {
+            let next = (current + 1 + rng.index(7)).min(pair[1]);
+            planted[current].push(devices[next].as_str());
+            current = next;
+        }
     }
-    Some(adjacency_list)
-}
 
-fn parse_node(input: &str) -> IResult<&str, &str> {
-    take(3usize)(input)
+    let mut input = String::new();
+    for (i, device) in devices.iter().enumerate().take(devices.len() - 1) {
+        let following = &devices[i + 1..(i + 8).min(devices.len())];
+        let mut outputs: Vec<_> = (0..rng.range(1..=3))
+            .filter_map(|_| rng.choose(following).map(String::as_str))
+            .chain(planted[i].iter().copied())
+            .collect();
+        outputs.sort_unstable();
+        outputs.dedup();
+        input += &format!("{device}: {}\n", outputs.join(" "));
+    }
+    input
 }
 
 #[cfg(test)]
@@ -104,7 +124,22 @@ mod tests {
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(0));
+        let result = part_two(&advent_of_code::template::read_file_part(
+            "examples", DAY, 2,
+        ));
+        assert_eq!(result, Some(2));
+    }
+
+    #[test]
+    fn test_generate() {
+        let graph = Graph::parse_adjacency(&generate(1, 100)).unwrap();
+        assert_eq!(graph.len(), 100 + 5);
+        assert!(!graph.has_cycle());
+        // devices only connect to the next few in order, so even the shortest route is long.
+        let [svr, out] = ["svr", "out"].map(|name| graph.id(name).unwrap());
+        assert!(graph.bfs(svr)[out] >= Some(100 / 7));
+        for seed in 0..10 {
+            assert!(part_two(&generate(seed, 20)).is_some_and(|paths| paths > 0));
+        }
     }
 }
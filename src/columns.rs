@@ -0,0 +1,115 @@
+/// Column-oriented views of fixed-width text, e.g. for puzzles that lay numbers out vertically.
+use std::ops::Range;
+
+use crate::grid::{Grid, Point};
+
+/// Reads `input` as a character matrix, padding lines shorter than the longest one with spaces.
+pub fn padded_grid(input: &str) -> Grid<char> {
+    let width = input.lines().map(|l| l.chars().count()).max().unwrap_or(0);
+    let mut cells = Vec::with_capacity(width * input.lines().count());
+    for line in input.lines() {
+        let len_before = cells.len();
+        cells.extend(line.chars());
+        cells.resize(len_before + width, ' ');
+    }
+    Grid::from_vec(width, cells).unwrap_or_else(|| Grid::new(0, 0, ' '))
+}
+
+/// Splits the columns of `grid` into blocks separated by columns consisting only of spaces.
+/// Separator columns are not part of any block.
+pub fn column_blocks(grid: &Grid<char>) -> Vec<Range<usize>> {
+    let mut blocks = vec![];
+    let mut start = None;
+    for col in 0..grid.width() {
+        let blank = grid.column(col).into_iter().flatten().all(|&c| c == ' ');
+        match (blank, start) {
+            (false, None) => start = Some(col),
+            (true, Some(s)) => {
+                blocks.push(s..col);
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        blocks.push(s..grid.width());
+    }
+    blocks
+}
+
+/// Reads one number per row of the `rows` × `cols` block, left to right. Rows without digits are skipped.
+/// Returns [`None`] if the block contains anything other than digits and spaces, or a number overflows.
+pub fn horizontal_numbers(
+    grid: &Grid<char>,
+    rows: Range<usize>,
+    cols: Range<usize>,
+) -> Option<Vec<u64>> {
+    read_numbers(rows.map(|row| {
+        cols.clone()
+            .filter_map(move |col| grid.get(Point::new(row, col)).copied())
+    }))
+}
+
+/// Reads one number per column of the `rows` × `cols` block, top to bottom. Columns without digits are skipped.
+/// Returns [`None`] if the block contains anything other than digits and spaces, or a number overflows.
+pub fn vertical_numbers(
+    grid: &Grid<char>,
+    rows: Range<usize>,
+    cols: Range<usize>,
+) -> Option<Vec<u64>> {
+    read_numbers(cols.map(|col| {
+        rows.clone()
+            .filter_map(move |row| grid.get(Point::new(row, col)).copied())
+    }))
+}
+
+/// Concatenates the digits of each line of characters, ignoring spaces and skipping lines without digits.
+fn read_numbers<I: Iterator<Item = char>>(lines: impl Iterator<Item = I>) -> Option<Vec<u64>> {
+    let mut numbers = vec![];
+    for line in lines {
+        let mut value: Option<u64> = None;
+        for c in line.filter(|&c| c != ' ') {
+            let digit = u64::from(c.to_digit(10)?);
+            value = Some(value.unwrap_or(0).checked_mul(10)?.checked_add(digit)?);
+        }
+        numbers.extend(value);
+    }
+    Some(numbers)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{column_blocks, horizontal_numbers, padded_grid, vertical_numbers};
+
+    #[test]
+    fn pads_ragged_lines() {
+        let grid = padded_grid("ab\nc\n\ndef\n");
+        assert_eq!((grid.width(), grid.height()), (3, 4));
+        assert_eq!(grid.row(1), Some(&['c', ' ', ' '][..]));
+        assert_eq!(padded_grid("").width(), 0);
+    }
+
+    #[test]
+    fn splits_column_blocks() {
+        let grid = padded_grid("12  3\n 4 56\n");
+        assert_eq!(column_blocks(&grid), vec![0..2, 3..5]);
+        assert!(column_blocks(&padded_grid("   \n")).is_empty());
+    }
+
+    #[test]
+    fn reads_numbers_in_both_directions() {
+        let grid = padded_grid("10 \n 0 \n   \n405\n");
+        assert_eq!(
+            horizontal_numbers(&grid, 0..4, 0..3),
+            Some(vec![10, 0, 405])
+        );
+        assert_eq!(vertical_numbers(&grid, 0..4, 0..3), Some(vec![14, 0, 5]));
+        assert_eq!(vertical_numbers(&grid, 0..2, 1..3), Some(vec![0]));
+
+        let grid = padded_grid("1+\n99999999999999999999\n");
+        assert_eq!(horizontal_numbers(&grid, 0..1, 0..2), None);
+        assert_eq!(horizontal_numbers(&grid, 1..2, 0..20), None);
+    }
+}
@@ -0,0 +1,145 @@
+/// Union-find over the elements `0..n`, e.g. for merging clusters.
+#[derive(Debug, Clone)]
+pub struct DisjointSet {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    count: usize,
+}
+
+impl DisjointSet {
+    /// Creates `n` singleton components.
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+            count: n,
+        }
+    }
+
+    /// The number of elements.
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// The number of components.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the representative of the component containing `x`, compressing the path to it.
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+
+        let mut current = x;
+        while self.parent[current] != root {
+            let next = self.parent[current];
+            self.parent[current] = root;
+            current = next;
+        }
+
+        root
+    }
+
+    /// Merges the components containing `a` and `b`, attaching the smaller one to the larger one.
+    /// Returns `false` if both were already in the same component.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        if self.size[a] < self.size[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        self.count -= 1;
+        true
+    }
+
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// The size of the component containing `x`.
+    pub fn size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    /// Iterates the sizes of all components.
+    pub fn component_sizes(&self) -> impl Iterator<Item = usize> + '_ {
+        self.parent
+            .iter()
+            .enumerate()
+            .filter(|&(i, &p)| i == p)
+            .map(|(i, _)| self.size[i])
+    }
+
+    /// Returns the members of all components, each sorted, ordered by their smallest member.
+    pub fn components(&mut self) -> Vec<Vec<usize>> {
+        let mut index_of_root = vec![usize::MAX; self.len()];
+        let mut components: Vec<Vec<usize>> = vec![];
+
+        for x in 0..self.len() {
+            let root = self.find(x);
+            if index_of_root[root] == usize::MAX {
+                index_of_root[root] = components.len();
+                components.push(vec![]);
+            }
+            components[index_of_root[root]].push(x);
+        }
+
+        components
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::DisjointSet;
+
+    #[test]
+    fn merges_components() {
+        let mut set = DisjointSet::new(6);
+        assert_eq!(set.count(), 6);
+        assert!(set.union(0, 1));
+        assert!(set.union(2, 1));
+        assert!(!set.union(0, 2));
+        assert!(set.union(4, 5));
+        assert_eq!(set.count(), 3);
+        assert!(set.connected(0, 2));
+        assert!(!set.connected(0, 3));
+        assert_eq!(set.size(2), 3);
+        assert_eq!(set.size(3), 1);
+    }
+
+    #[test]
+    fn iterates_components() {
+        let mut set = DisjointSet::new(5);
+        set.union(3, 1);
+        set.union(4, 0);
+        let mut sizes: Vec<_> = set.component_sizes().collect();
+        sizes.sort_unstable();
+        assert_eq!(sizes, vec![1, 2, 2]);
+        assert_eq!(set.components(), vec![vec![0, 4], vec![1, 3], vec![2]]);
+    }
+
+    #[test]
+    fn handles_long_chains() {
+        let n = 100_000;
+        let mut set = DisjointSet::new(n);
+        for i in 1..n {
+            set.union(i - 1, i);
+        }
+        assert_eq!(set.count(), 1);
+        assert_eq!(set.size(0), n);
+    }
+}
@@ -0,0 +1,341 @@
+/// Integer 3D points and closest-pair queries that avoid sorting all pairwise distances.
+use std::{cmp::Reverse, collections::BinaryHeap, iter::FusedIterator};
+
+use crate::disjoint_set::DisjointSet;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Point3 {
+    pub const fn new(x: i64, y: i64, z: i64) -> Self {
+        Self { x, y, z }
+    }
+
+    /// The exact squared euclidean distance. Saturates instead of overflowing for huge coordinates.
+    pub fn distance_squared(&self, other: &Self) -> u128 {
+        [
+            self.x.abs_diff(other.x),
+            self.y.abs_diff(other.y),
+            self.z.abs_diff(other.z),
+        ]
+        .into_iter()
+        .map(|d| u128::from(d) * u128::from(d))
+        .fold(0, u128::saturating_add)
+    }
+
+    fn axis(&self, axis: usize) -> i64 {
+        match axis {
+            0 => self.x,
+            1 => self.y,
+            _ => self.z,
+        }
+    }
+}
+
+/// Two points, identified by their index, and the squared distance between them. `a < b` always holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Pair {
+    pub distance_squared: u128,
+    pub a: usize,
+    pub b: usize,
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A k-d tree over a fixed set of points for nearest-neighbour queries.
+#[derive(Debug, Clone)]
+pub struct KdTree {
+    points: Vec<Point3>,
+    /// Point indices in tree order: the median of each range is its node, splitting on `depth % 3`.
+    order: Vec<usize>,
+}
+
+impl KdTree {
+    pub fn new(points: Vec<Point3>) -> Self {
+        let mut order: Vec<usize> = (0..points.len()).collect();
+        build(&points, &mut order, 0);
+        Self { points, order }
+    }
+
+    pub fn points(&self) -> &[Point3] {
+        &self.points
+    }
+
+    /// Returns the `k` points closest to `query` as `(distance_squared, index)`, closest first.
+    /// Ties are broken by index, so the result for `k` is always a prefix of the result for `k + 1`.
+    pub fn nearest(&self, query: &Point3, k: usize) -> Vec<(u128, usize)> {
+        let mut heap = BinaryHeap::with_capacity(k + 1);
+        if k > 0 {
+            self.search(&self.order, 0, query, k, &mut heap);
+        }
+        heap.into_sorted_vec()
+    }
+
+    fn search(
+        &self,
+        order: &[usize],
+        depth: usize,
+        query: &Point3,
+        k: usize,
+        heap: &mut BinaryHeap<(u128, usize)>,
+    ) {
+        if order.is_empty() {
+            return;
+        }
+
+        let mid = order.len() / 2;
+        let index = order[mid];
+        let point = &self.points[index];
+
+        let candidate = (point.distance_squared(query), index);
+        if heap.len() < k {
+            heap.push(candidate);
+        } else if heap.peek().is_some_and(|worst| candidate < *worst) {
+            heap.pop();
+            heap.push(candidate);
+        }
+
+        let axis = depth % 3;
+        let diff = query.axis(axis).abs_diff(point.axis(axis));
+        let (near, far) = if query.axis(axis) < point.axis(axis) {
+            (&order[..mid], &order[mid + 1..])
+        } else {
+            (&order[mid + 1..], &order[..mid])
+        };
+
+        self.search(near, depth + 1, query, k, heap);
+
+        // NOTE: equal distances are searched as well, so that ties are resolved by index.
+        let plane_distance = u128::from(diff) * u128::from(diff);
+        if heap.len() < k || heap.peek().is_some_and(|worst| plane_distance <= worst.0) {
+            self.search(far, depth + 1, query, k, heap);
+        }
+    }
+}
+
+fn build(points: &[Point3], order: &mut [usize], depth: usize) {
+    if order.len() <= 1 {
+        return;
+    }
+    let mid = order.len() / 2;
+    let axis = depth % 3;
+    order.select_nth_unstable_by_key(mid, |&i| points[i].axis(axis));
+    let (left, right) = order.split_at_mut(mid);
+    build(points, left, depth + 1);
+    build(points, &mut right[1..], depth + 1);
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Lazily yields all pairs of points in increasing distance order, ties ordered by `(a, b)`.
+/// Each point only queries as many neighbours from the tree as were actually consumed.
+pub struct ClosestPairs<'a> {
+    tree: &'a KdTree,
+    /// Per point, the neighbours with a higher index found so far, closest first.
+    neighbours: Vec<Vec<(u128, usize)>>,
+    /// Per point, how many neighbours were requested from the tree.
+    queried: Vec<usize>,
+    /// Per point, the position of the next neighbour to put into the heap.
+    cursor: Vec<usize>,
+    heap: BinaryHeap<Reverse<Pair>>,
+}
+
+const INITIAL_NEIGHBOURS: usize = 8;
+
+impl<'a> ClosestPairs<'a> {
+    pub fn new(tree: &'a KdTree) -> Self {
+        let n = tree.points.len();
+        let mut pairs = Self {
+            tree,
+            neighbours: vec![vec![]; n],
+            queried: vec![0; n],
+            cursor: vec![0; n],
+            heap: BinaryHeap::with_capacity(n),
+        };
+        for a in 0..n {
+            pairs.push_next(a);
+        }
+        pairs
+    }
+
+    fn push_next(&mut self, a: usize) {
+        let n = self.tree.points.len();
+        while self.cursor[a] >= self.neighbours[a].len() {
+            if self.queried[a] >= n {
+                return;
+            }
+            self.queried[a] = (self.queried[a] * 2).max(INITIAL_NEIGHBOURS).min(n);
+            self.neighbours[a] = self
+                .tree
+                .nearest(&self.tree.points[a], self.queried[a])
+                .into_iter()
+                .filter(|&(_, b)| b > a)
+                .collect();
+        }
+
+        let (distance_squared, b) = self.neighbours[a][self.cursor[a]];
+        self.cursor[a] += 1;
+        self.heap.push(Reverse(Pair {
+            distance_squared,
+            a,
+            b,
+        }));
+    }
+}
+
+impl Iterator for ClosestPairs<'_> {
+    type Item = Pair;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Reverse(pair) = self.heap.pop()?;
+        self.push_next(pair.a);
+        Some(pair)
+    }
+}
+
+impl FusedIterator for ClosestPairs<'_> {}
+
+/// Returns the `k` closest pairs of `points`, closest first.
+pub fn k_closest_pairs(points: &[Point3], k: usize) -> Vec<Pair> {
+    let tree = KdTree::new(points.to_vec());
+    ClosestPairs::new(&tree).take(k).collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Lazily yields the edges of a minimum spanning tree in increasing distance order (Kruskal's algorithm).
+/// Stops after the edge that connects all points.
+pub struct Kruskal<'a> {
+    pairs: ClosestPairs<'a>,
+    components: DisjointSet,
+}
+
+impl<'a> Kruskal<'a> {
+    pub fn new(tree: &'a KdTree) -> Self {
+        Self {
+            pairs: ClosestPairs::new(tree),
+            components: DisjointSet::new(tree.points.len()),
+        }
+    }
+
+    /// The components formed by the edges yielded so far.
+    pub fn components(&self) -> &DisjointSet {
+        &self.components
+    }
+}
+
+impl Iterator for Kruskal<'_> {
+    type Item = Pair;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.components.count() <= 1 {
+            return None;
+        }
+        self.pairs
+            .by_ref()
+            .find(|pair| self.components.union(pair.a, pair.b))
+    }
+}
+
+impl FusedIterator for Kruskal<'_> {}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{ClosestPairs, KdTree, Kruskal, Pair, Point3, k_closest_pairs};
+
+    fn get_mock_points(n: usize) -> Vec<Point3> {
+        // NOTE: small coordinates provoke plenty of equal distances.
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % 20) as i64 - 10
+        };
+        (0..n)
+            .map(|_| Point3::new(next(), next(), next()))
+            .collect()
+    }
+
+    fn brute_force_pairs(points: &[Point3]) -> Vec<Pair> {
+        let mut pairs = vec![];
+        for (a, p) in points.iter().enumerate() {
+            for (b, q) in points.iter().enumerate().skip(a + 1) {
+                pairs.push(Pair {
+                    distance_squared: p.distance_squared(q),
+                    a,
+                    b,
+                });
+            }
+        }
+        pairs.sort_unstable();
+        pairs
+    }
+
+    #[test]
+    fn computes_exact_distances() {
+        let a = Point3::new(-1, 2, 3);
+        let b = Point3::new(2, -2, 3);
+        assert_eq!(a.distance_squared(&b), 25);
+        let far = Point3::new(i64::MAX, i64::MAX, i64::MAX);
+        let near = Point3::new(i64::MIN, i64::MIN, i64::MIN);
+        assert_eq!(far.distance_squared(&near), u128::MAX);
+    }
+
+    #[test]
+    fn finds_nearest_neighbours() {
+        let points = get_mock_points(200);
+        let tree = KdTree::new(points.clone());
+        let query = Point3::new(1, 2, 3);
+        let mut expected: Vec<_> = points
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (p.distance_squared(&query), i))
+            .collect();
+        expected.sort_unstable();
+        assert_eq!(tree.nearest(&query, 17), expected[..17]);
+        assert_eq!(tree.nearest(&query, 500), expected);
+    }
+
+    #[test]
+    fn streams_all_pairs_in_order() {
+        let points = get_mock_points(150);
+        let tree = KdTree::new(points.clone());
+        let pairs: Vec<_> = ClosestPairs::new(&tree).collect();
+        assert_eq!(pairs, brute_force_pairs(&points));
+        assert_eq!(
+            k_closest_pairs(&points, 10),
+            brute_force_pairs(&points)[..10]
+        );
+    }
+
+    #[test]
+    fn builds_minimum_spanning_tree() {
+        let points = get_mock_points(100);
+        let tree = KdTree::new(points.clone());
+        let edges: Vec<_> = Kruskal::new(&tree).collect();
+        assert_eq!(edges.len(), points.len() - 1);
+        assert!(edges.is_sorted());
+
+        let mut components = crate::disjoint_set::DisjointSet::new(points.len());
+        let expected: Vec<_> = brute_force_pairs(&points)
+            .into_iter()
+            .filter(|p| components.union(p.a, p.b))
+            .collect();
+        assert_eq!(edges, expected);
+    }
+
+    #[test]
+    fn handles_tiny_inputs() {
+        assert!(k_closest_pairs(&[], 3).is_empty());
+        assert!(k_closest_pairs(&[Point3::default()], 3).is_empty());
+        let tree = KdTree::new(vec![Point3::default()]);
+        assert_eq!(Kruskal::new(&tree).count(), 0);
+    }
+}
@@ -0,0 +1,280 @@
+/// Linear algebra over GF(2), e.g. for puzzles where pressing a button toggles a set of lights.
+use std::ops::BitXorAssign;
+
+/// The largest null space dimension that [`Gf2Solution::min_weight`] enumerates, about a million steps.
+pub const MAX_NULL_SPACE: usize = 20;
+
+/// A fixed-length bitset stored in 64-bit words.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitSet {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl BitSet {
+    /// Creates a bitset of `len` unset bits.
+    pub fn new(len: usize) -> Self {
+        Self {
+            words: vec![0; len.div_ceil(64)],
+            len,
+        }
+    }
+
+    pub fn from_indices(len: usize, indices: impl IntoIterator<Item = usize>) -> Self {
+        let mut set = Self::new(len);
+        for i in indices {
+            set.set(i, true);
+        }
+        set
+    }
+
+    /// The number of bits.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, i: usize) -> bool {
+        assert!(i < self.len, "bit index out of range");
+        self.words[i / 64] >> (i % 64) & 1 == 1
+    }
+
+    pub fn set(&mut self, i: usize, value: bool) {
+        assert!(i < self.len, "bit index out of range");
+        let mask = 1 << (i % 64);
+        if value {
+            self.words[i / 64] |= mask;
+        } else {
+            self.words[i / 64] &= !mask;
+        }
+    }
+
+    pub fn toggle(&mut self, i: usize) {
+        self.set(i, !self.get(i));
+    }
+
+    /// The number of set bits.
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Iterates the indices of all set bits in ascending order.
+    pub fn ones(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(w, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                (word != 0).then(|| {
+                    let bit = word.trailing_zeros() as usize;
+                    word &= word - 1;
+                    w * 64 + bit
+                })
+            })
+        })
+    }
+}
+
+impl BitXorAssign<&BitSet> for BitSet {
+    fn bitxor_assign(&mut self, rhs: &BitSet) {
+        for (a, b) in self.words.iter_mut().zip(&rhs.words) {
+            *a ^= b;
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A system of linear equations over GF(2): every equation states that the XOR of some variables is `rhs`.
+#[derive(Debug, Clone, Default)]
+pub struct Gf2System {
+    variables: usize,
+    /// Each equation as a bitset over the variables, with the right-hand side stored in bit `variables`.
+    rows: Vec<BitSet>,
+}
+
+/// All solutions of a [`Gf2System`]: `particular` XOR any combination of the `null_space` basis vectors.
+#[derive(Debug, Clone)]
+pub struct Gf2Solution {
+    pub particular: BitSet,
+    pub null_space: Vec<BitSet>,
+}
+
+impl Gf2System {
+    pub fn new(variables: usize) -> Self {
+        Self {
+            variables,
+            rows: vec![],
+        }
+    }
+
+    /// Adds the equation `x[v1] ^ x[v2] ^ ... = rhs`. Variables listed twice cancel out.
+    pub fn equation(&mut self, variables: impl IntoIterator<Item = usize>, rhs: bool) -> &mut Self {
+        let mut row = BitSet::new(self.variables + 1);
+        for v in variables {
+            row.toggle(v);
+        }
+        row.set(self.variables, rhs);
+        self.rows.push(row);
+        self
+    }
+
+    /// Solves the system with Gaussian elimination. Returns [`None`] if it is inconsistent.
+    pub fn solve(&self) -> Option<Gf2Solution> {
+        let mut rows = self.rows.clone();
+        let mut pivots: Vec<usize> = vec![];
+
+        for column in 0..self.variables {
+            let rank = pivots.len();
+            let Some(pivot) = (rank..rows.len()).find(|&r| rows[r].get(column)) else {
+                continue;
+            };
+            rows.swap(rank, pivot);
+            let pivot_row = rows[rank].clone();
+            // NOTE: clears the column in all other rows to reach reduced row echelon form.
+            for (r, row) in rows.iter_mut().enumerate() {
+                if r != rank && row.get(column) {
+                    *row ^= &pivot_row;
+                }
+            }
+            pivots.push(column);
+        }
+
+        if rows[pivots.len()..]
+            .iter()
+            .any(|row| row.get(self.variables))
+        {
+            return None;
+        }
+
+        let mut particular = BitSet::new(self.variables);
+        for (row, &column) in rows.iter().zip(&pivots) {
+            particular.set(column, row.get(self.variables));
+        }
+
+        let mut is_pivot = vec![false; self.variables];
+        for &column in &pivots {
+            is_pivot[column] = true;
+        }
+        let null_space = (0..self.variables)
+            .filter(|&free| !is_pivot[free])
+            .map(|free| {
+                let mut vector = BitSet::new(self.variables);
+                vector.set(free, true);
+                for (row, &column) in rows.iter().zip(&pivots) {
+                    vector.set(column, row.get(free));
+                }
+                vector
+            })
+            .collect();
+
+        Some(Gf2Solution {
+            particular,
+            null_space,
+        })
+    }
+}
+
+impl Gf2Solution {
+    /// Returns a solution with the fewest set variables by enumerating the null space.
+    /// Takes `2^k` steps for a null space of dimension `k`, so returns [`None`] if `k` exceeds
+    /// [`MAX_NULL_SPACE`].
+    pub fn min_weight(&self) -> Option<BitSet> {
+        if self.null_space.len() > MAX_NULL_SPACE {
+            return None;
+        }
+
+        let mut current = self.particular.clone();
+        let mut best = current.clone();
+        let mut best_weight = best.count_ones();
+
+        // NOTE: walks the null space in Gray code order, so each step only adds a single basis vector.
+        for step in 1..1u64 << self.null_space.len() {
+            current ^= &self.null_space[step.trailing_zeros() as usize];
+            let weight = current.count_ones();
+            if weight < best_weight {
+                best_weight = weight;
+                best.clone_from(&current);
+            }
+        }
+
+        Some(best)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{BitSet, Gf2System, MAX_NULL_SPACE};
+
+    #[test]
+    fn handles_multi_word_bitsets() {
+        let mut set = BitSet::from_indices(130, [0, 63, 64, 129]);
+        assert_eq!(set.count_ones(), 4);
+        assert!(set.get(64));
+        set.toggle(64);
+        assert!(!set.get(64));
+        set ^= &BitSet::from_indices(130, [0, 100]);
+        assert_eq!(set.ones().collect::<Vec<_>>(), vec![63, 100, 129]);
+    }
+
+    #[test]
+    fn finds_minimum_weight_solutions() {
+        // NOTE: buttons `(3) (1,3) (2) (2,3) (0,2) (0,1)` and lights `.##.` need two presses.
+        let buttons: [&[usize]; 6] = [&[3], &[1, 3], &[2], &[2, 3], &[0, 2], &[0, 1]];
+        let lights = [false, true, true, false];
+        let mut system = Gf2System::new(buttons.len());
+        for (light, on) in lights.into_iter().enumerate() {
+            let pressing = (0..buttons.len()).filter(|&b| buttons[b].contains(&light));
+            system.equation(pressing, on);
+        }
+        let solution = system.solve().unwrap();
+        assert_eq!(solution.null_space.len(), 2);
+        assert_eq!(solution.min_weight().unwrap().count_ones(), 2);
+    }
+
+    #[test]
+    fn detects_inconsistent_systems() {
+        let mut system = Gf2System::new(2);
+        system
+            .equation([0, 1], true)
+            .equation([0], false)
+            .equation([1], false);
+        assert!(system.solve().is_none());
+    }
+
+    #[test]
+    fn solves_systems_with_many_variables() {
+        // NOTE: a chain `x[i] ^ x[i + 1] = 1` with `x[0] = 0` alternates.
+        let n = 150;
+        let mut system = Gf2System::new(n);
+        system.equation([0], false);
+        for i in 0..n - 1 {
+            system.equation([i, i + 1], true);
+        }
+        let solution = system.solve().unwrap();
+        assert!(solution.null_space.is_empty());
+        let expected = BitSet::from_indices(n, (1..n).step_by(2));
+        assert_eq!(solution.min_weight(), Some(expected));
+    }
+
+    #[test]
+    fn limits_null_space_enumeration() {
+        // NOTE: a single equation `x[0] = 1` leaves every other variable free.
+        let solve = |variables: usize| {
+            let mut system = Gf2System::new(variables);
+            system.equation([0], true);
+            system.solve().unwrap()
+        };
+
+        let solution = solve(MAX_NULL_SPACE + 1);
+        assert_eq!(solution.null_space.len(), MAX_NULL_SPACE);
+        let expected = BitSet::from_indices(MAX_NULL_SPACE + 1, [0]);
+        assert_eq!(solution.min_weight(), Some(expected));
+
+        assert_eq!(solve(MAX_NULL_SPACE + 2).min_weight(), None);
+        assert_eq!(solve(100).min_weight(), None);
+    }
+}
@@ -0,0 +1,386 @@
+/// Directed graphs with string-interned node names.
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+};
+
+use nom::{IResult, Parser, bytes::complete::take_while1};
+
+use crate::parsers::{key_values, lines, parse_all};
+
+/// The maximum number of required nodes for [`Graph::count_paths_through`].
+pub const MAX_REQUIRED: usize = 16;
+
+/// A directed graph with weighted edges. Nodes are addressed by dense ids in `0..len()`.
+#[derive(Debug, Clone, Default)]
+pub struct Graph {
+    names: Vec<String>,
+    ids: HashMap<String, usize>,
+    edges: Vec<Vec<(usize, u64)>>,
+}
+
+impl Graph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses adjacency lines such as `aaa: bbb ccc`, one line per node with outgoing edges.
+    /// Every edge gets a weight of `1`.
+    pub fn parse_adjacency(input: &str) -> Option<Self> {
+        let adjacency = parse_all(lines(key_values(parse_name, parse_name)), input).ok()?;
+
+        let mut graph = Self::new();
+        for (from, targets) in adjacency {
+            let from = graph.intern(from);
+            for to in targets {
+                let to = graph.intern(to);
+                graph.add_edge(from, to, 1);
+            }
+        }
+        Some(graph)
+    }
+
+    /// Returns the id for `name`, adding a node if it does not exist yet.
+    pub fn intern(&mut self, name: &str) -> usize {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+        let id = self.names.len();
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        self.edges.push(vec![]);
+        id
+    }
+
+    pub fn id(&self, name: &str) -> Option<usize> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: usize) -> &str {
+        &self.names[id]
+    }
+
+    /// The number of nodes.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn add_edge(&mut self, from: usize, to: usize, weight: u64) {
+        self.edges[from].push((to, weight));
+    }
+
+    /// Iterates the targets of all outgoing edges of `id`.
+    pub fn neighbours(&self, id: usize) -> impl Iterator<Item = usize> + '_ {
+        self.edges[id].iter().map(|&(to, _)| to)
+    }
+
+    /// Returns a graph with all edges reversed, keeping node ids.
+    pub fn reversed(&self) -> Self {
+        let mut edges = vec![vec![]; self.len()];
+        for (from, targets) in self.edges.iter().enumerate() {
+            for &(to, weight) in targets {
+                edges[to].push((from, weight));
+            }
+        }
+        Self {
+            names: self.names.clone(),
+            ids: self.ids.clone(),
+            edges,
+        }
+    }
+
+    /* ---------------------------------------------------------------------- */
+
+    /// Returns all nodes ordered so that every edge points forward, or [`None`] if the graph has a cycle.
+    pub fn topological_order(&self) -> Option<Vec<usize>> {
+        let mut in_degree = vec![0usize; self.len()];
+        for to in (0..self.len()).flat_map(|id| self.neighbours(id)) {
+            in_degree[to] += 1;
+        }
+
+        let mut queue: VecDeque<usize> = (0..self.len()).filter(|&id| in_degree[id] == 0).collect();
+        let mut order = Vec::with_capacity(self.len());
+
+        while let Some(id) = queue.pop_front() {
+            order.push(id);
+            for to in self.neighbours(id) {
+                in_degree[to] -= 1;
+                if in_degree[to] == 0 {
+                    queue.push_back(to);
+                }
+            }
+        }
+
+        (order.len() == self.len()).then_some(order)
+    }
+
+    pub fn has_cycle(&self) -> bool {
+        self.topological_order().is_none()
+    }
+
+    /// Counts the distinct paths from `from` to `to`. Returns [`None`] if a cycle is reachable from `from`.
+    /// Counts saturate at `u64::MAX`.
+    pub fn count_paths(&self, from: usize, to: usize) -> Option<u64> {
+        self.count_paths_through(from, to, &[])
+    }
+
+    /// Counts the distinct paths from `from` to `to` that visit all `required` nodes, in any order.
+    /// Returns [`None`] if a cycle is reachable from `from` or there are more than [`MAX_REQUIRED`] required nodes.
+    /// Counts saturate at `u64::MAX`.
+    pub fn count_paths_through(&self, from: usize, to: usize, required: &[usize]) -> Option<u64> {
+        if required.len() > MAX_REQUIRED {
+            return None;
+        }
+        let mut bits = vec![0usize; self.len()];
+        for (i, &id) in required.iter().enumerate() {
+            bits[id] |= 1 << i;
+        }
+        let masks = 1 << required.len();
+
+        // NOTE: a memoised DFS, kept iterative to stay safe on long chains. `ways[id][mask]` counts the
+        // paths from `id` to `to` that visit exactly the required nodes in `mask`.
+        let mut ways: Vec<Option<Vec<u64>>> = vec![None; self.len()];
+        let mut on_stack = vec![false; self.len()];
+        let mut stack = vec![(from, 0)];
+        on_stack[from] = true;
+
+        while let Some((id, edge)) = stack.pop() {
+            if id != to
+                && let Some(&(next, _)) = self.edges[id].get(edge)
+            {
+                stack.push((id, edge + 1));
+                if on_stack[next] {
+                    return None;
+                }
+                if ways[next].is_none() {
+                    on_stack[next] = true;
+                    stack.push((next, 0));
+                }
+                continue;
+            }
+
+            let mut counts = vec![0u64; masks];
+            if id == to {
+                counts[bits[id]] = 1;
+            } else {
+                for next in self.neighbours(id) {
+                    for (mask, &count) in ways[next].as_ref()?.iter().enumerate() {
+                        let mask = mask | bits[id];
+                        counts[mask] = counts[mask].saturating_add(count);
+                    }
+                }
+            }
+            on_stack[id] = false;
+            ways[id] = Some(counts);
+        }
+
+        Some(ways[from].as_ref()?[masks - 1])
+    }
+
+    /* ---------------------------------------------------------------------- */
+
+    /// Returns the number of edges on a shortest path from `start` to every node, [`None`] if unreachable.
+    pub fn bfs(&self, start: usize) -> Vec<Option<usize>> {
+        let mut distances = vec![None; self.len()];
+        let mut queue = VecDeque::from([start]);
+        distances[start] = Some(0);
+
+        while let Some(id) = queue.pop_front() {
+            let distance = distances[id].unwrap_or_default();
+            for next in self.neighbours(id) {
+                if distances[next].is_none() {
+                    distances[next] = Some(distance + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// Returns all nodes reachable from `start` in depth-first preorder.
+    pub fn dfs(&self, start: usize) -> Vec<usize> {
+        let mut visited = vec![false; self.len()];
+        let mut stack = vec![start];
+        let mut order = vec![];
+
+        while let Some(id) = stack.pop() {
+            if visited[id] {
+                continue;
+            }
+            visited[id] = true;
+            order.push(id);
+            // NOTE: pushed in reverse so that neighbours are visited in edge order.
+            let mut neighbours: Vec<_> = self.neighbours(id).filter(|&n| !visited[n]).collect();
+            neighbours.reverse();
+            stack.extend(neighbours);
+        }
+
+        order
+    }
+
+    /// Returns the total weight of a lightest path from `start` to every node, [`None`] if unreachable.
+    pub fn dijkstra(&self, start: usize) -> Vec<Option<u64>> {
+        let mut distances = vec![None; self.len()];
+        let mut heap = BinaryHeap::from([Reverse((0u64, start))]);
+
+        while let Some(Reverse((distance, id))) = heap.pop() {
+            if distances[id].is_some() {
+                continue;
+            }
+            distances[id] = Some(distance);
+            for &(next, weight) in &self.edges[id] {
+                if distances[next].is_none() {
+                    heap.push(Reverse((distance.saturating_add(weight), next)));
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// Returns the strongly connected components, each sorted, in topological order of the condensation.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<usize>> {
+        // NOTE: Kosaraju's algorithm with iterative DFS to stay safe on long chains.
+        let mut visited = vec![false; self.len()];
+        let mut finished = Vec::with_capacity(self.len());
+
+        for root in 0..self.len() {
+            if visited[root] {
+                continue;
+            }
+            visited[root] = true;
+            let mut stack = vec![(root, 0)];
+            while let Some((id, edge)) = stack.pop() {
+                match self.edges[id].get(edge) {
+                    Some(&(next, _)) => {
+                        stack.push((id, edge + 1));
+                        if !visited[next] {
+                            visited[next] = true;
+                            stack.push((next, 0));
+                        }
+                    }
+                    None => finished.push(id),
+                }
+            }
+        }
+
+        let reversed = self.reversed();
+        let mut component_of = vec![usize::MAX; self.len()];
+        let mut components = vec![];
+
+        for &root in finished.iter().rev() {
+            if component_of[root] != usize::MAX {
+                continue;
+            }
+            let mut component = vec![];
+            let mut stack = vec![root];
+            component_of[root] = components.len();
+            while let Some(id) = stack.pop() {
+                component.push(id);
+                for next in reversed.neighbours(id) {
+                    if component_of[next] == usize::MAX {
+                        component_of[next] = components.len();
+                        stack.push(next);
+                    }
+                }
+            }
+            component.sort_unstable();
+            components.push(component);
+        }
+
+        components
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn parse_name(input: &str) -> IResult<&str, &str> {
+    take_while1(|c: char| !c.is_whitespace() && c != ':').parse(input)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Graph;
+
+    fn get_mock_graph() -> Graph {
+        Graph::parse_adjacency("a: b c\nb: d\nc: d e\nd: out\ne: out\n").unwrap()
+    }
+
+    #[test]
+    fn parses_adjacency_lines() {
+        let graph = get_mock_graph();
+        assert_eq!(graph.len(), 6);
+        let a = graph.id("a").unwrap();
+        assert_eq!(graph.name(a), "a");
+        let names: Vec<_> = graph.neighbours(a).map(|id| graph.name(id)).collect();
+        assert_eq!(names, vec!["b", "c"]);
+        assert!(Graph::parse_adjacency("long_name: x\nx: y").is_some());
+        assert!(Graph::parse_adjacency("a b c").is_none());
+    }
+
+    #[test]
+    fn counts_paths() {
+        let graph = get_mock_graph();
+        let id = |name| graph.id(name).unwrap();
+        assert_eq!(graph.count_paths(id("a"), id("out")), Some(3));
+        assert_eq!(graph.count_paths(id("b"), id("e")), Some(0));
+        assert_eq!(
+            graph.count_paths_through(id("a"), id("out"), &[id("d"), id("c")]),
+            Some(1)
+        );
+        assert_eq!(
+            graph.count_paths_through(id("a"), id("out"), &[id("b"), id("e")]),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn detects_cycles() {
+        let graph = get_mock_graph();
+        assert!(!graph.has_cycle());
+        let order = graph.topological_order().unwrap();
+        assert_eq!(order.first(), graph.id("a").as_ref());
+        assert_eq!(order.last(), graph.id("out").as_ref());
+
+        let cyclic = Graph::parse_adjacency("a: b\nb: c\nc: a d\n").unwrap();
+        assert!(cyclic.has_cycle());
+        assert_eq!(cyclic.count_paths(0, 3), None);
+
+        // NOTE: only cycles reachable from the start of the paths matter.
+        let partly_cyclic = Graph::parse_adjacency("a: b\nb: c\nc: b\nd: e\n").unwrap();
+        assert_eq!(partly_cyclic.count_paths(3, 4), Some(1));
+        assert_eq!(partly_cyclic.count_paths(0, 4), None);
+    }
+
+    #[test]
+    fn searches_graphs() {
+        let mut graph = get_mock_graph();
+        let id = |graph: &Graph, name| graph.id(name).unwrap();
+        let (a, out) = (id(&graph, "a"), id(&graph, "out"));
+        assert_eq!(graph.bfs(a)[out], Some(3));
+        assert_eq!(graph.bfs(out)[a], None);
+        let names: Vec<_> = graph.dfs(a).into_iter().map(|i| graph.name(i)).collect();
+        assert_eq!(names, vec!["a", "b", "d", "out", "c", "e"]);
+
+        let shortcut = graph.intern("shortcut");
+        graph.add_edge(a, shortcut, 1);
+        graph.add_edge(shortcut, out, 5);
+        assert_eq!(graph.dijkstra(a)[out], Some(3));
+        graph.add_edge(a, out, 2);
+        assert_eq!(graph.dijkstra(a)[out], Some(2));
+    }
+
+    #[test]
+    fn finds_strongly_connected_components() {
+        let graph = Graph::parse_adjacency("a: b\nb: c\nc: a d\nd: e\ne: d\n").unwrap();
+        let components = graph.strongly_connected_components();
+        assert_eq!(components, vec![vec![0, 1, 2], vec![3, 4]]);
+    }
+}
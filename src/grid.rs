@@ -0,0 +1,329 @@
+/// A two-dimensional grid with flat, row-major storage.
+use std::ops::{Index, IndexMut};
+
+/// A position in a [`Grid`], addressed by row and column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Point {
+    pub const fn new(row: usize, col: usize) -> Self {
+        Self { row, col }
+    }
+
+    /// Moves one step into `direction`, returns [`None`] if that would leave the non-negative quadrant.
+    pub fn step(self, direction: Direction) -> Option<Self> {
+        let (dr, dc) = direction.offset();
+        Some(Self {
+            row: self.row.checked_add_signed(dr)?,
+            col: self.col.checked_add_signed(dc)?,
+        })
+    }
+}
+
+/// One of the eight directions on a grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+impl Direction {
+    /// The four orthogonal directions.
+    pub const CARDINAL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// All eight directions, orthogonal and diagonal.
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+    ];
+
+    /// Returns the `(row, col)` offset of a single step into this direction.
+    pub const fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1),
+            Direction::Right => (0, 1),
+            Direction::UpLeft => (-1, -1),
+            Direction::UpRight => (-1, 1),
+            Direction::DownLeft => (1, -1),
+            Direction::DownRight => (1, 1),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid of the given size with every cell set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Creates a grid from row-major `cells`, returns [`None`] if they don't fill whole rows.
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Option<Self> {
+        if width == 0 {
+            return cells.is_empty().then_some(Self {
+                width,
+                height: 0,
+                cells,
+            });
+        }
+        if !cells.len().is_multiple_of(width) {
+            return None;
+        }
+        Some(Self {
+            width,
+            height: cells.len() / width,
+            cells,
+        })
+    }
+
+    /// Parses a character map, mapping each character with `f`.
+    /// Returns [`None`] if `f` rejects a character or rows differ in length.
+    /// A trailing newline is ignored.
+    pub fn parse(input: &str, mut f: impl FnMut(char) -> Option<T>) -> Option<Self> {
+        let mut width = None;
+        let mut cells = Vec::with_capacity(input.len());
+
+        for line in input.lines() {
+            let len_before = cells.len();
+            for c in line.chars() {
+                cells.push(f(c)?);
+            }
+            let row_width = cells.len() - len_before;
+            if *width.get_or_insert(row_width) != row_width {
+                return None;
+            }
+        }
+
+        Self::from_vec(width.unwrap_or(0), cells)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        point.row < self.height && point.col < self.width
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        if self.contains(point) {
+            self.cells.get(point.row * self.width + point.col)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        if self.contains(point) {
+            self.cells.get_mut(point.row * self.width + point.col)
+        } else {
+            None
+        }
+    }
+
+    /// Moves one step from `point` into `direction`, returns [`None`] if that leaves the grid.
+    pub fn step(&self, point: Point, direction: Direction) -> Option<Point> {
+        point.step(direction).filter(|p| self.contains(*p))
+    }
+
+    /// Iterates the up to four orthogonal neighbours of `point` that lie inside the grid.
+    pub fn neighbours4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        Direction::CARDINAL
+            .into_iter()
+            .filter_map(move |d| self.step(point, d))
+    }
+
+    /// Iterates the up to eight orthogonal and diagonal neighbours of `point` that lie inside the grid.
+    pub fn neighbours8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |d| self.step(point, d))
+    }
+
+    /// Iterates all points in row-major order.
+    pub fn points(&self) -> impl Iterator<Item = Point> + use<T> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| Point::new(row, col)))
+    }
+
+    /// Iterates all cells together with their position in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    /// Returns the first position holding a cell that matches `predicate`.
+    pub fn position(&self, predicate: impl Fn(&T) -> bool) -> Option<Point> {
+        self.iter().find(|(_, v)| predicate(v)).map(|(p, _)| p)
+    }
+
+    pub fn row(&self, row: usize) -> Option<&[T]> {
+        if row < self.height {
+            Some(&self.cells[row * self.width..(row + 1) * self.width])
+        } else {
+            None
+        }
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // NOTE: `chunks` panics on a chunk size of zero.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, col: usize) -> Option<impl Iterator<Item = &T>> {
+        if col < self.width {
+            Some(self.cells.iter().skip(col).step_by(self.width))
+        } else {
+            None
+        }
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).filter_map(|col| self.column(col))
+    }
+
+    /// Returns a copy of this grid with rows and columns swapped.
+    pub fn transposed(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width: self.height,
+            height: self.width,
+            cells: self.columns().flatten().cloned().collect(),
+        }
+    }
+
+    /// Applies `f` to every cell, keeping the layout.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        self.get(point).expect("point out of grid bounds")
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut Self::Output {
+        self.get_mut(point).expect("point out of grid bounds")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Direction, Grid, Point};
+
+    fn get_mock_grid() -> Grid<u32> {
+        Grid::parse("123\n456\n", |c| c.to_digit(10)).unwrap()
+    }
+
+    #[test]
+    fn parses_character_maps() {
+        let grid = get_mock_grid();
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid[Point::new(1, 2)], 6);
+        assert!(Grid::parse("12\n345", |c| c.to_digit(10)).is_none());
+        assert!(Grid::parse("12\n3x", |c| c.to_digit(10)).is_none());
+        assert_eq!(Grid::parse("", |c| c.to_digit(10)).unwrap().height(), 0);
+    }
+
+    #[test]
+    fn checks_bounds() {
+        let grid = get_mock_grid();
+        assert_eq!(grid.get(Point::new(2, 0)), None);
+        assert_eq!(grid.get(Point::new(0, 3)), None);
+        assert_eq!(grid.step(Point::new(0, 0), Direction::Up), None);
+        assert_eq!(
+            grid.step(Point::new(0, 0), Direction::DownRight),
+            Some(Point::new(1, 1))
+        );
+    }
+
+    #[test]
+    fn iterates_neighbours() {
+        let grid = get_mock_grid();
+        assert_eq!(grid.neighbours4(Point::new(0, 0)).count(), 2);
+        assert_eq!(grid.neighbours8(Point::new(0, 0)).count(), 3);
+        assert_eq!(grid.neighbours8(Point::new(1, 1)).count(), 5);
+        let sum: u32 = grid.neighbours4(Point::new(0, 1)).map(|p| grid[p]).sum();
+        assert_eq!(sum, 1 + 3 + 5);
+    }
+
+    #[test]
+    fn provides_row_and_column_views() {
+        let grid = get_mock_grid();
+        assert_eq!(grid.row(1), Some(&[4, 5, 6][..]));
+        assert_eq!(grid.row(2), None);
+        assert_eq!(
+            grid.column(1).unwrap().copied().collect::<Vec<_>>(),
+            vec![2, 5]
+        );
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.columns().count(), 3);
+
+        let transposed = grid.transposed();
+        assert_eq!(transposed.width(), 2);
+        assert_eq!(transposed.height(), 3);
+        assert_eq!(transposed.row(2), Some(&[3, 6][..]));
+    }
+
+    #[test]
+    fn iterates_points_in_row_major_order() {
+        let grid = get_mock_grid();
+        let values: Vec<_> = grid.iter().map(|(_, v)| *v).collect();
+        assert_eq!(values, vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(grid.position(|v| *v == 5), Some(Point::new(1, 1)));
+    }
+}
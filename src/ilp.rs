@@ -0,0 +1,448 @@
+/// Integer linear programs of the form "minimise the sum of `x` subject to `A·x = b`, `x >= 0`".
+use std::{error::Error, fmt::Display};
+
+use good_lp::{
+    Expression, ResolutionError, Solution as _, SolverModel, constraint, microlp, variable,
+    variable::ProblemVariables,
+};
+
+/// Solver values further than this from an integer are treated as a solver failure.
+const INTEGER_TOLERANCE: f64 = 1e-6;
+
+/// The maximum number of search nodes [`IntegerProgram::solve_exact`] visits before giving up.
+const EXACT_NODE_LIMIT: u64 = 50_000_000;
+
+/// A system of linear equations over non-negative integer variables.
+#[derive(Debug, Clone, Default)]
+pub struct IntegerProgram {
+    variables: usize,
+    /// Each equation as sparse `(variable, coefficient)` terms and its right-hand side.
+    equations: Vec<(Vec<(usize, i64)>, i64)>,
+}
+
+/// An optimal assignment of the variables of an [`IntegerProgram`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solution {
+    pub values: Vec<u64>,
+}
+
+impl Solution {
+    /// The value of the objective, the sum of all variables.
+    pub fn total(&self) -> u64 {
+        self.values.iter().sum()
+    }
+}
+
+impl IntegerProgram {
+    pub fn new(variables: usize) -> Self {
+        Self {
+            variables,
+            equations: vec![],
+        }
+    }
+
+    /// Adds the equation `sum(coefficient * x[variable]) = rhs`. Repeated variables are summed up.
+    /// Returns [`IlpError::UnknownVariable`] and leaves the program unchanged if a term refers to a
+    /// variable the program does not have.
+    pub fn equation(
+        &mut self,
+        terms: impl IntoIterator<Item = (usize, i64)>,
+        rhs: i64,
+    ) -> Result<&mut Self, IlpError> {
+        let mut coefficients = vec![0; self.variables];
+        for (variable, coefficient) in terms {
+            let Some(sum) = coefficients.get_mut(variable) else {
+                return Err(IlpError::UnknownVariable {
+                    variable,
+                    variables: self.variables,
+                });
+            };
+            *sum += coefficient;
+        }
+        let terms = coefficients
+            .into_iter()
+            .enumerate()
+            .filter(|&(_, c)| c != 0)
+            .collect();
+        self.equations.push((terms, rhs));
+        Ok(self)
+    }
+
+    /// Solves the program with `good_lp` and verifies the rounded result against all equations.
+    /// Falls back to [`IntegerProgram::solve_exact`] if the solver fails or returns a non-integral result.
+    pub fn solve(&self) -> Result<Solution, IlpError> {
+        match self.solve_lp() {
+            Err(e @ (IlpError::NotIntegral | IlpError::Solver(_))) => {
+                self.solve_exact().map_err(|exact| match exact {
+                    // NOTE: the solver's error is more telling if the exact search cannot run.
+                    IlpError::Unsupported | IlpError::SearchLimit => e,
+                    exact => exact,
+                })
+            }
+            result => result,
+        }
+    }
+
+    fn solve_lp(&self) -> Result<Solution, IlpError> {
+        let mut problem = ProblemVariables::new();
+        let vars: Vec<_> = (0..self.variables)
+            .map(|_| problem.add(variable().integer().min(0)))
+            .collect();
+        let objective: Expression = vars.iter().sum();
+
+        let mut model = problem.minimise(objective).using(microlp);
+        for (terms, rhs) in &self.equations {
+            let lhs: Expression = terms
+                .iter()
+                .map(|&(variable, coefficient)| coefficient as f64 * vars[variable])
+                .sum();
+            model = model.with(constraint!(lhs == *rhs as f64));
+        }
+
+        let solution = model.solve().map_err(|e| match e {
+            ResolutionError::Infeasible => IlpError::Infeasible,
+            ResolutionError::Unbounded => IlpError::Unbounded,
+            e => IlpError::Solver(e.to_string()),
+        })?;
+
+        let values = vars
+            .iter()
+            .map(|&v| {
+                let value = solution.value(v);
+                let rounded = value.round();
+                ((value - rounded).abs() < INTEGER_TOLERANCE && rounded >= 0.0)
+                    .then_some(rounded as u64)
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or(IlpError::NotIntegral)?;
+
+        let solution = Solution { values };
+        if self.is_satisfied_by(&solution) {
+            Ok(solution)
+        } else {
+            Err(IlpError::NotIntegral)
+        }
+    }
+
+    /// Returns whether `solution` satisfies every equation, computed without rounding errors.
+    pub fn is_satisfied_by(&self, solution: &Solution) -> bool {
+        solution.values.len() == self.variables
+            && self.equations.iter().all(|(terms, rhs)| {
+                let lhs: i128 = terms
+                    .iter()
+                    .map(|&(v, c)| i128::from(c) * i128::from(solution.values[v]))
+                    .sum();
+                lhs == i128::from(*rhs)
+            })
+    }
+
+    /// Solves the program with an exact branch-and-bound search, used as a fallback by [`IntegerProgram::solve`]
+    /// and to cross-check it.
+    /// Only supports non-negative coefficients, so that every variable is bounded by the right-hand sides.
+    /// Intended for small systems.
+    pub fn solve_exact(&self) -> Result<Solution, IlpError> {
+        if self
+            .equations
+            .iter()
+            .any(|(terms, _)| terms.iter().any(|&(_, c)| c < 0))
+        {
+            return Err(IlpError::Unsupported);
+        }
+        if self.equations.iter().any(|(_, rhs)| *rhs < 0) {
+            return Err(IlpError::Infeasible);
+        }
+
+        // NOTE: dense non-negative coefficients, `coefficients[variable][equation]`.
+        let mut coefficients = vec![vec![0u64; self.equations.len()]; self.variables];
+        for (e, (terms, _)) in self.equations.iter().enumerate() {
+            for &(v, c) in terms {
+                coefficients[v][e] = c as u64;
+            }
+        }
+
+        let mut search = ExactSearch {
+            coefficients,
+            residual: self.equations.iter().map(|&(_, rhs)| rhs as u64).collect(),
+            values: vec![None; self.variables],
+            best: None,
+            nodes: 0,
+        };
+        search.run(0)?;
+
+        search
+            .best
+            .map(|(_, values)| Solution { values })
+            .ok_or(IlpError::Infeasible)
+    }
+}
+
+struct ExactSearch {
+    coefficients: Vec<Vec<u64>>,
+    residual: Vec<u64>,
+    values: Vec<Option<u64>>,
+    best: Option<(u64, Vec<u64>)>,
+    nodes: u64,
+}
+
+impl ExactSearch {
+    /// The largest value `variable` can take without exceeding any residual.
+    fn upper_bound(&self, variable: usize) -> u64 {
+        self.coefficients[variable]
+            .iter()
+            .zip(&self.residual)
+            .filter(|&(&c, _)| c > 0)
+            .map(|(&c, &r)| r / c)
+            .min()
+            .unwrap_or(0)
+    }
+
+    fn free_variables(&self, equation: usize) -> impl Iterator<Item = usize> + '_ {
+        (0..self.values.len())
+            .filter(move |&v| self.values[v].is_none() && self.coefficients[v][equation] > 0)
+    }
+
+    fn assign(&mut self, variable: usize, value: u64) {
+        self.values[variable] = Some(value);
+        for (residual, &c) in self.residual.iter_mut().zip(&self.coefficients[variable]) {
+            *residual -= c * value;
+        }
+    }
+
+    fn unassign(&mut self, variable: usize) {
+        let value = self.values[variable].take().unwrap_or(0);
+        for (residual, &c) in self.residual.iter_mut().zip(&self.coefficients[variable]) {
+            *residual += c * value;
+        }
+    }
+
+    fn run(&mut self, total: u64) -> Result<(), IlpError> {
+        self.nodes += 1;
+        if self.nodes > EXACT_NODE_LIMIT {
+            return Err(IlpError::SearchLimit);
+        }
+
+        // NOTE: branches on the unsatisfied equation with the fewest free variables.
+        // Its remaining value also gives a lower bound for the sum of the free variables.
+        let mut bound = 0;
+        let mut choice: Option<(usize, usize)> = None;
+        for e in 0..self.residual.len() {
+            if self.residual[e] == 0 {
+                continue;
+            }
+            let free: Vec<_> = self.free_variables(e).collect();
+            let Some(max_coefficient) = free.iter().map(|&v| self.coefficients[v][e]).max() else {
+                return Ok(());
+            };
+            bound = bound.max(self.residual[e].div_ceil(max_coefficient));
+            if choice.is_none_or(|(count, _)| free.len() < count) {
+                choice = Some((free.len(), e));
+            }
+        }
+
+        let best_total = self.best.as_ref().map_or(u64::MAX, |(t, _)| *t);
+        if total + bound >= best_total {
+            return Ok(());
+        }
+
+        let Some((count, equation)) = choice else {
+            // NOTE: all equations are satisfied, so every unassigned variable is zero.
+            let values = self.values.iter().map(|v| v.unwrap_or(0)).collect();
+            self.best = Some((total, values));
+            return Ok(());
+        };
+
+        if count == 1 {
+            let variable = self.free_variables(equation).next().unwrap_or_default();
+            let c = self.coefficients[variable][equation];
+            let value = self.residual[equation] / c;
+            if self.residual[equation].is_multiple_of(c) && value <= self.upper_bound(variable) {
+                self.assign(variable, value);
+                let result = self.run(total + value);
+                self.unassign(variable);
+                result?;
+            }
+            return Ok(());
+        }
+
+        let Some(variable) = self
+            .free_variables(equation)
+            .min_by_key(|&v| self.upper_bound(v))
+        else {
+            return Ok(());
+        };
+
+        // NOTE: trying large values first finds a good incumbent early, which improves pruning.
+        for value in (0..=self.upper_bound(variable)).rev() {
+            self.assign(variable, value);
+            let result = self.run(total + value);
+            self.unassign(variable);
+            result?;
+        }
+
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An error which can be returned when solving an [`IntegerProgram`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IlpError {
+    /// No assignment of the variables satisfies all equations.
+    Infeasible,
+    /// The objective can be made arbitrarily small.
+    Unbounded,
+    /// The solver returned a result that is not an exact integer solution.
+    NotIntegral,
+    /// The exact solver does not support negative coefficients.
+    Unsupported,
+    /// The exact solver gave up because the search space is too large.
+    SearchLimit,
+    /// An equation refers to a variable that is not part of the program.
+    UnknownVariable { variable: usize, variables: usize },
+    /// The solver failed for another reason.
+    Solver(String),
+}
+
+impl Error for IlpError {}
+
+impl Display for IlpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IlpError::Infeasible => f.write_str("the program has no solution"),
+            IlpError::Unbounded => f.write_str("the program is unbounded"),
+            IlpError::NotIntegral => f.write_str("the solver did not return an integer solution"),
+            IlpError::Unsupported => {
+                f.write_str("the exact solver only supports non-negative coefficients")
+            }
+            IlpError::SearchLimit => f.write_str("the exact solver exceeded its search limit"),
+            IlpError::UnknownVariable {
+                variable,
+                variables,
+            } => write!(
+                f,
+                "variable {variable} is out of range for a program with {variables} variables"
+            ),
+            IlpError::Solver(e) => write!(f, "the solver failed: {e}"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{IlpError, IntegerProgram};
+    use crate::property::{Runner, int, vec};
+
+    /// Buttons `(3) (1,3) (2) (2,3) (0,2) (0,1)` with targets `{3,5,4,7}`.
+    fn get_mock_program() -> IntegerProgram {
+        let buttons: [&[usize]; 6] = [&[3], &[1, 3], &[2], &[2, 3], &[0, 2], &[0, 1]];
+        let targets = [3, 5, 4, 7];
+        let mut program = IntegerProgram::new(buttons.len());
+        for (i, target) in targets.into_iter().enumerate() {
+            program
+                .equation(
+                    buttons
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, b)| b.contains(&i))
+                        .map(|(v, _)| (v, 1)),
+                    target,
+                )
+                .unwrap();
+        }
+        program
+    }
+
+    #[test]
+    fn solves_programs() {
+        let program = get_mock_program();
+        let solution = program.solve().unwrap();
+        assert_eq!(solution.total(), 10);
+        assert!(program.is_satisfied_by(&solution));
+    }
+
+    #[test]
+    fn matches_exact_solver() {
+        let program = get_mock_program();
+        let exact = program.solve_exact().unwrap();
+        assert_eq!(exact.total(), program.solve().unwrap().total());
+        assert!(program.is_satisfied_by(&exact));
+    }
+
+    #[test]
+    fn matches_exact_solver_on_generated_programs() {
+        // NOTE: targets are the result of pressing random buttons, so every program is feasible.
+        let buttons = vec(vec(int(0u8..=5), 1..=4), 1..=8);
+        let presses = vec(int(0u8..=6), 8..=8);
+        Runner::new()
+            .cases(300)
+            .check(&(buttons, presses), |(buttons, presses)| {
+                let mut program = IntegerProgram::new(buttons.len());
+                for light in 0..6 {
+                    let pressing = buttons
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, b)| b.contains(&light));
+                    let target = pressing.clone().map(|(i, _)| i64::from(presses[i])).sum();
+                    program
+                        .equation(pressing.map(|(i, _)| (i, 1)), target)
+                        .unwrap();
+                }
+                match (program.solve(), program.solve_exact()) {
+                    (Ok(solution), Ok(exact)) => {
+                        solution.total() == exact.total()
+                            && program.is_satisfied_by(&solution)
+                            && program.is_satisfied_by(&exact)
+                    }
+                    _ => false,
+                }
+            });
+    }
+
+    #[test]
+    fn reports_infeasibility() {
+        let mut program = IntegerProgram::new(2);
+        program.equation([(0, 2), (1, 2)], 3).unwrap();
+        assert_eq!(program.solve_exact(), Err(IlpError::Infeasible));
+        assert!(program.solve().is_err());
+
+        let mut program = IntegerProgram::new(1);
+        program
+            .equation([(0, 1)], 1)
+            .and_then(|program| program.equation([(0, 1)], 2))
+            .unwrap();
+        assert_eq!(program.solve(), Err(IlpError::Infeasible));
+        assert_eq!(program.solve_exact(), Err(IlpError::Infeasible));
+    }
+
+    #[test]
+    fn zeroes_unconstrained_variables() {
+        let mut program = IntegerProgram::new(3);
+        program.equation([(0, 1), (2, 2)], 4).unwrap();
+        assert_eq!(program.solve_exact().unwrap().values, vec![0, 0, 2]);
+        assert_eq!(program.solve().unwrap().total(), 2);
+    }
+
+    #[test]
+    fn rejects_unsupported_exact_programs() {
+        let mut program = IntegerProgram::new(2);
+        program.equation([(0, 1), (1, -1)], 1).unwrap();
+        assert_eq!(program.solve_exact(), Err(IlpError::Unsupported));
+        assert_eq!(program.solve().unwrap().total(), 1);
+    }
+
+    #[test]
+    fn reports_unknown_variables() {
+        let mut program = IntegerProgram::new(2);
+        assert_eq!(
+            program.equation([(0, 1), (2, 1)], 1).err(),
+            Some(IlpError::UnknownVariable {
+                variable: 2,
+                variables: 2
+            })
+        );
+        assert_eq!(program.solve().unwrap().total(), 0);
+    }
+}
@@ -1,3 +1,17 @@
+pub mod automaton;
+pub mod beam;
+pub mod columns;
+pub mod disjoint_set;
+pub mod geometry;
+pub mod gf2;
+pub mod graph;
+pub mod grid;
+pub mod ilp;
+pub mod parsers;
+#[cfg(feature = "test_lib")]
+pub mod property;
+pub mod random;
+pub mod range_set;
 pub mod template;
 
 // Use this file to add helper functions and additional modules.
@@ -1,4 +1,6 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, crosscheck, download, fuzz, generate, read, scaffold, solve, time,
+};
 use args::{AppArguments, parse};
 
 #[cfg(feature = "today")]
@@ -7,7 +9,10 @@ use advent_of_code::template::Day;
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::{
+        Day, OutputFormat,
+        limits::{Limits, parse_memory, parse_timeout},
+    };
     use std::process;
 
     pub enum AppArguments {
@@ -17,6 +22,21 @@ mod args {
         Read {
             day: Day,
         },
+        Generate {
+            day: Day,
+            seed: u64,
+            size: usize,
+        },
+        Crosscheck {
+            day: Day,
+            cases: u64,
+            size: usize,
+        },
+        Fuzz {
+            day: Day,
+            iterations: u64,
+            seed: u64,
+        },
         Scaffold {
             day: Day,
             download: bool,
@@ -30,31 +50,63 @@ mod args {
         },
         All {
             release: bool,
+            limits: Limits,
+            store_answers: bool,
         },
         Time {
             all: bool,
             day: Option<Day>,
             store: bool,
+            output_format: Option<OutputFormat>,
+            out: Option<String>,
+            limits: Limits,
+            allocations: bool,
         },
         #[cfg(feature = "today")]
         Today,
     }
 
+    fn parse_limits(args: &mut pico_args::Arguments) -> Result<Limits, pico_args::Error> {
+        Ok(Limits {
+            timeout: args.opt_value_from_fn("--timeout", parse_timeout)?,
+            memory: args.opt_value_from_fn("--memory-limit", parse_memory)?,
+        })
+    }
+
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
+        parse_from(pico_args::Arguments::from_env())
+    }
 
+    fn parse_from(
+        mut args: pico_args::Arguments,
+    ) -> Result<AppArguments, Box<dyn std::error::Error>> {
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
+                limits: parse_limits(&mut args)?,
+                store_answers: args.contains("--store-answers"),
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let allocations = args.contains("--allocations");
+                let output_format = args.opt_value_from_str("--output-format")?;
+                let out: Option<String> = args.opt_value_from_str("--out")?;
 
+                if out.is_some() && output_format.is_none() {
+                    return Err("`--out` requires `--output-format` to be set.".into());
+                }
+
+                // NOTE: options go first, otherwise the free day argument would take e.g. `--timeout`.
+                let limits = parse_limits(&mut args)?;
                 AppArguments::Time {
                     all,
-                    day: args.opt_free_from_str()?,
                     store,
+                    output_format,
+                    out,
+                    limits,
+                    allocations,
+                    day: args.opt_free_from_str()?,
                 }
             }
             Some("download") => AppArguments::Download {
@@ -63,16 +115,31 @@ mod args {
             Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
             },
-            Some("scaffold") => AppArguments::Scaffold {
+            Some("generate") => AppArguments::Generate {
+                seed: args.opt_value_from_str("--seed")?.unwrap_or(0),
+                size: args.opt_value_from_str("--size")?.unwrap_or(1000),
+                day: args.free_from_str()?,
+            },
+            Some("crosscheck") => AppArguments::Crosscheck {
+                cases: args.opt_value_from_str("--cases")?.unwrap_or(100),
+                size: args.opt_value_from_str("--size")?.unwrap_or(20),
+                day: args.free_from_str()?,
+            },
+            Some("fuzz") => AppArguments::Fuzz {
+                iterations: args.opt_value_from_str("--iterations")?.unwrap_or(10000),
+                seed: args.opt_value_from_str("--seed")?.unwrap_or(0),
                 day: args.free_from_str()?,
+            },
+            Some("scaffold") => AppArguments::Scaffold {
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                day: args.free_from_str()?,
             },
             Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                day: args.free_from_str()?,
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -93,6 +160,63 @@ mod args {
 
         Ok(app_args)
     }
+
+    #[cfg(test)]
+    mod tests {
+        use super::{AppArguments, parse_from};
+        use std::{ffi::OsString, time::Duration};
+
+        fn parse_args(args: &[&str]) -> AppArguments {
+            parse_from(pico_args::Arguments::from_vec(
+                args.iter().map(OsString::from).collect(),
+            ))
+            .unwrap()
+        }
+
+        #[test]
+        fn parses_time_limits_without_day() {
+            let AppArguments::Time {
+                all, day, limits, ..
+            } = parse_args(&["time", "--all", "--timeout", "1s", "--memory-limit", "1G"])
+            else {
+                panic!("expected the time command");
+            };
+            assert!(all);
+            assert!(day.is_none());
+            assert_eq!(limits.timeout, Some(Duration::from_secs(1)));
+            assert_eq!(limits.memory, Some(1 << 30));
+        }
+
+        #[test]
+        fn parses_day_after_options() {
+            let AppArguments::Time {
+                day,
+                limits,
+                allocations,
+                ..
+            } = parse_args(&[
+                "time",
+                "--timeout",
+                "500ms",
+                "--allocations",
+                "7",
+                "--store",
+            ])
+            else {
+                panic!("expected the time command");
+            };
+            assert_eq!(day.map(|d| d.into_inner()), Some(7));
+            assert_eq!(limits.timeout, Some(Duration::from_millis(500)));
+            assert!(allocations);
+
+            let AppArguments::Generate { day, seed, size } =
+                parse_args(&["generate", "--seed", "3", "2"])
+            else {
+                panic!("expected the generate command");
+            };
+            assert_eq!((day.into_inner(), seed, size), (2, 3, 1000));
+        }
+    }
 }
 
 fn main() {
@@ -102,10 +226,29 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
+            AppArguments::All {
+                release,
+                limits,
+                store_answers,
+            } => all::handle(release, &limits, store_answers),
+            AppArguments::Time {
+                day,
+                all,
+                store,
+                output_format,
+                out,
+                limits,
+                allocations,
+            } => time::handle(day, all, store, output_format, out, &limits, allocations),
             AppArguments::Download { day } => download::handle(day),
             AppArguments::Read { day } => read::handle(day),
+            AppArguments::Generate { day, seed, size } => generate::handle(day, seed, size),
+            AppArguments::Crosscheck { day, cases, size } => crosscheck::handle(day, cases, size),
+            AppArguments::Fuzz {
+                day,
+                iterations,
+                seed,
+            } => fuzz::handle(day, iterations, seed),
             AppArguments::Scaffold {
                 day,
                 download,
@@ -0,0 +1,219 @@
+/// Shared nom parsers for common puzzle input shapes.
+use std::{error::Error, fmt::Display, str::FromStr};
+
+use nom::{
+    IResult, Parser,
+    bytes::complete::tag,
+    character::complete::{char, digit1, newline, none_of, one_of, space1},
+    combinator::{all_consuming, map_opt, map_res, opt, recognize},
+    error::ErrorKind,
+    multi::{many0, many1, separated_list0, separated_list1},
+    sequence::{preceded, separated_pair, terminated},
+};
+
+use crate::grid::Grid;
+
+type NomError<'a> = nom::error::Error<&'a str>;
+
+/// Parses an unsigned integer of any width, e.g. `unsigned::<u8>`. Fails on overflow.
+pub fn unsigned<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(digit1, str::parse).parse(input)
+}
+
+/// Parses an integer with an optional `+` or `-` sign, e.g. `signed::<i64>`. Fails on overflow.
+pub fn signed<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(recognize((opt(one_of("+-")), digit1)), str::parse).parse(input)
+}
+
+/// Parses one or more lines of `item`, separated by newlines.
+pub fn lines<'a, O>(
+    item: impl Parser<&'a str, Output = O, Error = NomError<'a>>,
+) -> impl Parser<&'a str, Output = Vec<O>, Error = NomError<'a>> {
+    separated_list1(newline, item)
+}
+
+/// Parses zero or more lines of `item`, separated by newlines. Accepts an empty input.
+pub fn lines0<'a, O>(
+    item: impl Parser<&'a str, Output = O, Error = NomError<'a>>,
+) -> impl Parser<&'a str, Output = Vec<O>, Error = NomError<'a>> {
+    separated_list0(newline, item)
+}
+
+/// Parses one or more `item`s separated by commas, e.g. `1,2,3`.
+pub fn comma_list<'a, O>(
+    item: impl Parser<&'a str, Output = O, Error = NomError<'a>>,
+) -> impl Parser<&'a str, Output = Vec<O>, Error = NomError<'a>> {
+    separated_list1(char(','), item)
+}
+
+/// Parses zero or more `item`s separated by commas. Accepts an empty input.
+pub fn comma_list0<'a, O>(
+    item: impl Parser<&'a str, Output = O, Error = NomError<'a>>,
+) -> impl Parser<&'a str, Output = Vec<O>, Error = NomError<'a>> {
+    separated_list0(char(','), item)
+}
+
+/// Parses one or more blocks separated by a blank line, e.g. `blocks(lines(unsigned::<u64>))`.
+pub fn blocks<'a, O>(
+    block: impl Parser<&'a str, Output = O, Error = NomError<'a>>,
+) -> impl Parser<&'a str, Output = Vec<O>, Error = NomError<'a>> {
+    separated_list1(tag("\n\n"), block)
+}
+
+/// Parses a rectangular character map into a [`Grid`], mapping each character with `f`.
+/// Fails if `f` rejects a character or rows differ in length.
+pub fn char_grid<T>(f: impl Fn(char) -> Option<T>) -> impl Fn(&str) -> IResult<&str, Grid<T>> {
+    move |input| {
+        map_opt(
+            separated_list1(newline, many1(map_opt(none_of("\n"), &f))),
+            |rows: Vec<Vec<T>>| {
+                let width = rows.first().map_or(0, Vec::len);
+                if rows.iter().any(|row| row.len() != width) {
+                    return None;
+                }
+                Grid::from_vec(width, rows.into_iter().flatten().collect())
+            },
+        )
+        .parse(input)
+    }
+}
+
+/// Parses a `key: value value ...` line. The list of values may be empty.
+pub fn key_values<'a, K, V>(
+    key: impl Parser<&'a str, Output = K, Error = NomError<'a>>,
+    value: impl Parser<&'a str, Output = V, Error = NomError<'a>>,
+) -> impl Parser<&'a str, Output = (K, Vec<V>), Error = NomError<'a>> {
+    separated_pair(key, char(':'), many0(preceded(space1, value)))
+}
+
+/// Runs `parser` on the whole `input`, allowing a single trailing newline.
+pub fn parse_all<'a, O>(
+    parser: impl Parser<&'a str, Output = O, Error = NomError<'a>>,
+    input: &'a str,
+) -> Result<O, ParseError> {
+    match all_consuming(terminated(parser, opt(newline))).parse(input) {
+        Ok((_, output)) => Ok(output),
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => Err(ParseError::new(input, &e)),
+        Err(nom::Err::Incomplete(_)) => Err(ParseError {
+            line: input.lines().count().max(1),
+            column: 1,
+            kind: ErrorKind::Complete,
+            snippet: String::new(),
+        }),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An error returned by [`parse_all`], pointing at the position where parsing failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line of the failure.
+    pub line: usize,
+    /// 1-based column of the failure, counted in characters.
+    pub column: usize,
+    /// The nom parser that failed.
+    pub kind: ErrorKind,
+    /// The remainder of the line at the failure.
+    pub snippet: String,
+}
+
+impl ParseError {
+    fn new(input: &str, error: &NomError<'_>) -> Self {
+        let offset = input.len() - error.input.len();
+        let consumed = &input[..offset];
+        let line_start = consumed.rfind('\n').map_or(0, |i| i + 1);
+
+        Self {
+            line: consumed.matches('\n').count() + 1,
+            column: consumed[line_start..].chars().count() + 1,
+            kind: error.code,
+            snippet: error.input.lines().next().unwrap_or_default().to_string(),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "parse error at line {}, column {} ({:?}) near `{}`",
+            self.line, self.column, self.kind, self.snippet
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        blocks, char_grid, comma_list, comma_list0, key_values, lines, lines0, parse_all, signed,
+        unsigned,
+    };
+    use nom::{Parser, character::complete::alpha1};
+
+    #[test]
+    fn parses_integers() {
+        assert_eq!(unsigned::<u8>("255,"), Ok((",", 255)));
+        assert!(unsigned::<u8>("256").is_err());
+        assert!(unsigned::<u64>("-1").is_err());
+        assert_eq!(signed::<i64>("-42x"), Ok(("x", -42)));
+        assert_eq!(signed::<i32>("+7"), Ok(("", 7)));
+        assert!(signed::<i8>("-129").is_err());
+    }
+
+    #[test]
+    fn parses_lists() {
+        let input = "1,2\n3\n\n-4,5\n";
+        let result = parse_all(blocks(lines(comma_list(signed::<i32>))), input);
+        assert_eq!(
+            result,
+            Ok(vec![vec![vec![1, 2], vec![3]], vec![vec![-4, 5]]])
+        );
+    }
+
+    #[test]
+    fn parses_empty_lists() {
+        assert_eq!(parse_all(lines0(unsigned::<u32>), ""), Ok(vec![]));
+        assert_eq!(parse_all(lines0(unsigned::<u32>), "\n"), Ok(vec![]));
+        assert_eq!(parse_all(comma_list0(unsigned::<u32>), ""), Ok(vec![]));
+        assert_eq!(parse_all(lines0(unsigned::<u32>), "1\n2\n"), Ok(vec![1, 2]));
+        assert!(parse_all(lines(unsigned::<u32>), "").is_err());
+        assert!(parse_all(comma_list(unsigned::<u32>), "").is_err());
+    }
+
+    #[test]
+    fn parses_grids_and_key_values() {
+        let grid = parse_all(char_grid(|c| c.to_digit(10)), "12\n34\n").unwrap();
+        assert_eq!(grid.width(), 2);
+        assert_eq!(grid.rows().nth(1), Some(&[3, 4][..]));
+        assert!(parse_all(char_grid(|c| c.to_digit(10)), "12\n3\n").is_err());
+
+        let (key, values) = key_values(alpha1, unsigned::<u32>)
+            .parse("abc: 1 2")
+            .unwrap()
+            .1;
+        assert_eq!((key, values), ("abc", vec![1, 2]));
+        assert_eq!(
+            key_values(alpha1, alpha1).parse("a:"),
+            Ok(("", ("a", vec![])))
+        );
+    }
+
+    #[test]
+    fn reports_error_positions() {
+        assert_eq!(
+            parse_all(lines(unsigned::<u32>), "1\n2").unwrap(),
+            vec![1, 2]
+        );
+        assert!(parse_all(lines(unsigned::<u32>), "1\n2\n\n").is_err());
+
+        let error = parse_all(lines(comma_list(unsigned::<u32>)), "1,2\n3,x4\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.snippet, ",x4");
+        assert!(error.to_string().contains("line 2, column 2"));
+    }
+}
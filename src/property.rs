@@ -0,0 +1,380 @@
+/// Property-based testing: checks that a property holds for many random values and shrinks failures
+/// to a minimal counterexample.
+use std::{fmt::Debug, ops::RangeInclusive};
+
+use crate::random::Rng;
+
+/// The number of random values checked by default.
+pub const DEFAULT_CASES: u32 = 100;
+
+/// The maximum number of successful shrinking steps before giving up on finding a smaller counterexample.
+const MAX_SHRINKS: u32 = 1000;
+
+/// A way to generate random values and to propose simpler versions of a value.
+pub trait Strategy {
+    type Value: Clone + Debug;
+
+    fn generate(&self, rng: &mut Rng) -> Self::Value;
+
+    /// Returns candidates that are simpler than `value`, the most promising first.
+    fn shrink(&self, value: &Self::Value) -> Vec<Self::Value>;
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Integers in a range, shrinking towards the value closest to zero.
+#[derive(Debug, Clone)]
+pub struct Int<T> {
+    start: i128,
+    end: i128,
+    phantom: std::marker::PhantomData<T>,
+}
+
+/// Generates integers of any primitive type in `range`, with a bias towards the bounds.
+pub fn int<T: Copy + Into<i128>>(range: RangeInclusive<T>) -> Int<T> {
+    let (start, end) = range.into_inner();
+    let (start, end) = (start.into(), end.into());
+    assert!(start <= end, "empty integer range");
+    Int {
+        start,
+        end,
+        phantom: std::marker::PhantomData,
+    }
+}
+
+impl<T: Copy + Debug + Into<i128> + TryFrom<i128>> Int<T> {
+    fn origin(&self) -> i128 {
+        0.clamp(self.start, self.end)
+    }
+
+    fn from_i128(value: i128) -> T {
+        T::try_from(value).unwrap_or_else(|_| unreachable!("values stay within the range"))
+    }
+}
+
+impl<T: Copy + Debug + Into<i128> + TryFrom<i128>> Strategy for Int<T> {
+    type Value = T;
+
+    fn generate(&self, rng: &mut Rng) -> T {
+        let value = match rng.below(10) {
+            0 => self.start,
+            1 => self.end,
+            2 => self.origin(),
+            // NOTE: a range of `i64` or `u64` spans at most `2^64 - 1` values above its start.
+            _ => self.start + i128::from(rng.range(0..=(self.end - self.start) as u64)),
+        };
+        Self::from_i128(value)
+    }
+
+    fn shrink(&self, value: &T) -> Vec<T> {
+        let value: i128 = (*value).into();
+        let origin = self.origin();
+        let mut candidates = vec![];
+        let mut distance = value - origin;
+        while distance != 0 {
+            candidates.push(Self::from_i128(value - distance));
+            distance /= 2;
+        }
+        candidates
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Vectors of values from another strategy, shrinking by removing and then simplifying elements.
+#[derive(Debug, Clone)]
+pub struct VecOf<S> {
+    element: S,
+    len: RangeInclusive<usize>,
+}
+
+pub fn vec<S: Strategy>(element: S, len: RangeInclusive<usize>) -> VecOf<S> {
+    VecOf { element, len }
+}
+
+impl<S: Strategy> Strategy for VecOf<S> {
+    type Value = Vec<S::Value>;
+
+    fn generate(&self, rng: &mut Rng) -> Self::Value {
+        let len = rng.range(*self.len.start() as u64..=*self.len.end() as u64) as usize;
+        (0..len).map(|_| self.element.generate(rng)).collect()
+    }
+
+    fn shrink(&self, value: &Self::Value) -> Vec<Self::Value> {
+        let mut candidates = vec![];
+
+        // NOTE: removes halves, then quarters and so on down to single elements.
+        let removable = value.len().saturating_sub(*self.len.start());
+        let mut chunk = removable.min(value.len() / 2).max(removable.min(1));
+        while chunk > 0 {
+            for start in (0..=value.len() - chunk).step_by(chunk) {
+                candidates.push([&value[..start], &value[start + chunk..]].concat());
+            }
+            chunk /= 2;
+        }
+
+        for (i, element) in value.iter().enumerate() {
+            for simpler in self.element.shrink(element) {
+                let mut candidate = value.clone();
+                candidate[i] = simpler;
+                candidates.push(candidate);
+            }
+        }
+        candidates
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Strings of characters from an alphabet, shrinking by removing characters and replacing them with
+/// the first character of the alphabet.
+#[derive(Debug, Clone)]
+pub struct StringOf {
+    alphabet: Vec<char>,
+    chars: VecOf<Int<u32>>,
+}
+
+pub fn string(alphabet: &str, len: RangeInclusive<usize>) -> StringOf {
+    let alphabet: Vec<char> = alphabet.chars().collect();
+    assert!(!alphabet.is_empty(), "empty alphabet");
+    StringOf {
+        chars: vec(int(0..=alphabet.len() as u32 - 1), len),
+        alphabet,
+    }
+}
+
+impl Strategy for StringOf {
+    type Value = String;
+
+    fn generate(&self, rng: &mut Rng) -> String {
+        let indices = self.chars.generate(rng);
+        indices
+            .into_iter()
+            .map(|i| self.alphabet[i as usize])
+            .collect()
+    }
+
+    fn shrink(&self, value: &String) -> Vec<String> {
+        let indices: Vec<u32> = value
+            .chars()
+            .map(|c| self.alphabet.iter().position(|&a| a == c).unwrap_or(0) as u32)
+            .collect();
+        self.chars
+            .shrink(&indices)
+            .into_iter()
+            .map(|candidate| {
+                candidate
+                    .into_iter()
+                    .map(|i| self.alphabet[i as usize])
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl<A: Strategy, B: Strategy> Strategy for (A, B) {
+    type Value = (A::Value, B::Value);
+
+    fn generate(&self, rng: &mut Rng) -> Self::Value {
+        (self.0.generate(rng), self.1.generate(rng))
+    }
+
+    fn shrink(&self, value: &Self::Value) -> Vec<Self::Value> {
+        let first = self.0.shrink(&value.0).into_iter();
+        let second = self.1.shrink(&value.1).into_iter();
+        first
+            .map(|a| (a, value.1.clone()))
+            .chain(second.map(|b| (value.0.clone(), b)))
+            .collect()
+    }
+}
+
+impl<A: Strategy, B: Strategy, C: Strategy> Strategy for (A, B, C) {
+    type Value = (A::Value, B::Value, C::Value);
+
+    fn generate(&self, rng: &mut Rng) -> Self::Value {
+        (
+            self.0.generate(rng),
+            self.1.generate(rng),
+            self.2.generate(rng),
+        )
+    }
+
+    fn shrink(&self, value: &Self::Value) -> Vec<Self::Value> {
+        let (a, b, c) = value;
+        let first = self.0.shrink(a).into_iter();
+        let second = self.1.shrink(b).into_iter();
+        let third = self.2.shrink(c).into_iter();
+        first
+            .map(|x| (x, b.clone(), c.clone()))
+            .chain(second.map(|x| (a.clone(), x, c.clone())))
+            .chain(third.map(|x| (a.clone(), b.clone(), x)))
+            .collect()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A counterexample found by [`Runner::run`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure<T> {
+    /// The index of the random case that failed first.
+    pub case: u32,
+    /// The value that failed first.
+    pub original: T,
+    /// The simplest failing value found by shrinking `original`.
+    pub minimal: T,
+    pub shrinks: u32,
+}
+
+/// Checks properties against random values. Deterministic for a given seed.
+#[derive(Debug, Clone)]
+pub struct Runner {
+    cases: u32,
+    seed: u64,
+}
+
+impl Default for Runner {
+    fn default() -> Self {
+        Self {
+            cases: DEFAULT_CASES,
+            seed: 0,
+        }
+    }
+}
+
+impl Runner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of random values to check.
+    pub fn cases(mut self, cases: u32) -> Self {
+        self.cases = cases;
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Checks `property` for random values of `strategy`. Returns the shrunk first counterexample if it fails.
+    pub fn run<S: Strategy>(
+        &self,
+        strategy: &S,
+        property: impl Fn(&S::Value) -> bool,
+    ) -> Result<(), Failure<S::Value>> {
+        let mut rng = Rng::new(self.seed);
+        for case in 0..self.cases {
+            let value = strategy.generate(&mut rng);
+            if property(&value) {
+                continue;
+            }
+
+            let mut minimal = value.clone();
+            let mut shrinks = 0;
+            while shrinks < MAX_SHRINKS {
+                let Some(simpler) = strategy
+                    .shrink(&minimal)
+                    .into_iter()
+                    .find(|candidate| !property(candidate))
+                else {
+                    break;
+                };
+                minimal = simpler;
+                shrinks += 1;
+            }
+
+            return Err(Failure {
+                case,
+                original: value,
+                minimal,
+                shrinks,
+            });
+        }
+        Ok(())
+    }
+
+    /// Like [`Runner::run`], but panics with the minimal counterexample, for use in tests.
+    pub fn check<S: Strategy>(&self, strategy: &S, property: impl Fn(&S::Value) -> bool) {
+        if let Err(failure) = self.run(strategy, property) {
+            panic!(
+                "property failed in case {} (seed {}): {:?}, shrunk {} times from {:?}",
+                failure.case, self.seed, failure.minimal, failure.shrinks, failure.original
+            );
+        }
+    }
+}
+
+/// Checks `property` for [`DEFAULT_CASES`] random values of `strategy` and panics with the minimal counterexample.
+pub fn check<S: Strategy>(strategy: &S, property: impl Fn(&S::Value) -> bool) {
+    Runner::new().check(strategy, property);
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Runner, Strategy, check, int, string, vec};
+    use crate::random::Rng;
+
+    #[test]
+    fn generates_values_within_bounds() {
+        let mut rng = Rng::new(1);
+        let strategy = (int(-5i64..=5), vec(int(10u8..=20), 2..=4));
+        for _ in 0..200 {
+            let (n, v) = strategy.generate(&mut rng);
+            assert!((-5..=5).contains(&n));
+            assert!((2..=4).contains(&v.len()));
+            assert!(v.iter().all(|x| (10..=20).contains(x)));
+        }
+        let s = string("ab", 0..=3).generate(&mut rng);
+        assert!(s.len() <= 3 && s.chars().all(|c| "ab".contains(c)));
+        int(0..=u64::MAX).generate(&mut rng);
+        int(i64::MIN..=i64::MAX).generate(&mut rng);
+    }
+
+    #[test]
+    fn shrinks_integers_towards_zero() {
+        let failure = Runner::new()
+            .run(&int(-1000i32..=1000), |&n| n < 37)
+            .unwrap_err();
+        assert_eq!(failure.minimal, 37);
+
+        let failure = Runner::new()
+            .run(&int(-1000i64..=-10), |&n| n > -500)
+            .unwrap_err();
+        assert_eq!(failure.minimal, -500);
+    }
+
+    #[test]
+    fn shrinks_vectors_and_strings() {
+        let failure = Runner::new()
+            .run(&vec(int(0u32..=100), 0..=20), |v| {
+                v.iter().sum::<u32>() < 50
+            })
+            .unwrap_err();
+        assert_eq!(failure.minimal, vec![50]);
+
+        let failure = Runner::new()
+            .run(&string("abc", 0..=10), |s| !s.contains('c'))
+            .unwrap_err();
+        assert_eq!(failure.minimal, "c");
+
+        let failure = Runner::new()
+            .run(&vec(int(0u8..=9), 3..=8), |v| v.len() < 5)
+            .unwrap_err();
+        assert_eq!(failure.minimal, vec![0; 5]);
+    }
+
+    #[test]
+    fn passes_true_properties() {
+        check(&(int(0u64..=1000), int(0u64..=1000)), |&(a, b)| {
+            a + b >= a.max(b)
+        });
+        assert!(Runner::new().cases(0).run(&int(0u8..=1), |_| false).is_ok());
+    }
+}
@@ -0,0 +1,120 @@
+/// A small deterministic pseudo-random number generator, e.g. for generating puzzle inputs.
+use std::ops::RangeInclusive;
+
+/// A SplitMix64 generator. The same seed always produces the same sequence, on every platform.
+/// Not suitable for cryptography.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a uniformly distributed value in `0..n`, or `0` if `n` is zero.
+    pub fn below(&mut self, n: u64) -> u64 {
+        if n == 0 {
+            return 0;
+        }
+        // NOTE: rejects the values of the last, incomplete copy of `0..n` to avoid modulo bias.
+        let limit = u64::MAX - u64::MAX % n;
+        loop {
+            let value = self.next_u64();
+            if value < limit {
+                return value % n;
+            }
+        }
+    }
+
+    /// Returns a uniformly distributed value in `range`. Returns the start of an empty range.
+    pub fn range(&mut self, range: RangeInclusive<u64>) -> u64 {
+        let (start, end) = range.into_inner();
+        if start >= end {
+            return start;
+        }
+        match (end - start).checked_add(1) {
+            Some(len) => start + self.below(len),
+            None => self.next_u64(),
+        }
+    }
+
+    /// Returns a uniformly distributed index in `0..len`, or `0` if `len` is zero.
+    pub fn index(&mut self, len: usize) -> usize {
+        self.below(len as u64) as usize
+    }
+
+    /// Returns `true` with the given probability.
+    pub fn chance(&mut self, probability: f64) -> bool {
+        // NOTE: uses the top 53 bits, the precision of an `f64` in `0.0..1.0`.
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < probability
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        items.get(self.index(items.len()))
+    }
+
+    /// Shuffles `items` uniformly with the Fisher-Yates algorithm.
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.index(i + 1));
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Rng;
+
+    #[test]
+    fn is_deterministic() {
+        let a: Vec<_> = std::iter::repeat_with({
+            let mut rng = Rng::new(42);
+            move || rng.next_u64()
+        })
+        .take(5)
+        .collect();
+        let mut rng = Rng::new(42);
+        assert!(a.iter().all(|&v| v == rng.next_u64()));
+        assert_ne!(Rng::new(1).next_u64(), Rng::new(2).next_u64());
+    }
+
+    #[test]
+    fn stays_within_bounds() {
+        let mut rng = Rng::new(7);
+        let mut seen = [false; 6];
+        for _ in 0..1000 {
+            let value = rng.range(10..=15);
+            assert!((10..=15).contains(&value));
+            seen[(value - 10) as usize] = true;
+        }
+        assert!(seen.iter().all(|&s| s));
+        assert_eq!(rng.range(5..=5), 5);
+        assert_eq!(rng.below(0), 0);
+        rng.range(0..=u64::MAX);
+        assert!(!rng.chance(0.0));
+        assert!(rng.chance(1.0));
+        assert_eq!(rng.choose::<u8>(&[]), None);
+    }
+
+    #[test]
+    fn shuffles_permutations() {
+        let mut rng = Rng::new(3);
+        let mut items: Vec<_> = (0..50).collect();
+        rng.shuffle(&mut items);
+        assert_ne!(items, (0..50).collect::<Vec<_>>());
+        items.sort_unstable();
+        assert_eq!(items, (0..50).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,244 @@
+/// A set of `u64` values stored as sorted, disjoint inclusive ranges.
+use std::ops::RangeInclusive;
+
+use nom::{
+    IResult, Parser, character::complete::char, combinator::map_opt, multi::separated_list1,
+    sequence::separated_pair,
+};
+
+use crate::parsers::unsigned;
+
+/// Overlapping and adjacent ranges are merged on insertion, so every value is covered by at most one range.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct RangeSet {
+    ranges: Vec<(u64, u64)>,
+}
+
+impl RangeSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds all values of `range`. Empty ranges are ignored.
+    pub fn insert(&mut self, range: RangeInclusive<u64>) {
+        let (mut start, mut end) = range.into_inner();
+        if start > end {
+            return;
+        }
+
+        // NOTE: ranges in `from..to` overlap or touch the new range and are merged into it.
+        let from = self
+            .ranges
+            .partition_point(|r| r.1.saturating_add(1) < start);
+        let to = self
+            .ranges
+            .partition_point(|r| r.0 <= end.saturating_add(1));
+
+        if from < to {
+            start = start.min(self.ranges[from].0);
+            end = end.max(self.ranges[to - 1].1);
+        }
+        self.ranges.splice(from..to, [(start, end)]);
+    }
+
+    pub fn contains(&self, value: u64) -> bool {
+        let index = self.ranges.partition_point(|r| r.1 < value);
+        self.ranges.get(index).is_some_and(|r| r.0 <= value)
+    }
+
+    /// The number of covered values. Saturates at `u64::MAX` if the set covers every `u64`.
+    pub fn total_len(&self) -> u64 {
+        self.ranges
+            .iter()
+            .map(|&(start, end)| (end - start).saturating_add(1))
+            .fold(0, u64::saturating_add)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Iterates the merged ranges in ascending order.
+    pub fn ranges(&self) -> impl Iterator<Item = RangeInclusive<u64>> + '_ {
+        self.ranges.iter().map(|&(start, end)| start..=end)
+    }
+
+    /// Iterates the uncovered ranges between the first and the last covered value.
+    pub fn gaps(&self) -> impl Iterator<Item = RangeInclusive<u64>> + '_ {
+        self.ranges
+            .windows(2)
+            .map(|pair| pair[0].1 + 1..=pair[1].0 - 1)
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for range in other.ranges() {
+            result.insert(range);
+        }
+        result
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+        let (mut i, mut j) = (0, 0);
+
+        while let (Some(a), Some(b)) = (self.ranges.get(i), other.ranges.get(j)) {
+            let start = a.0.max(b.0);
+            let end = a.1.min(b.1);
+            if start <= end {
+                ranges.push((start, end));
+            }
+            if a.1 < b.1 {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        Self { ranges }
+    }
+
+    /// Returns the values of `self` that are not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+        let mut j = 0;
+
+        for &(range_start, end) in &self.ranges {
+            // NOTE: ranges of `other` ending before this range can't affect any later range either.
+            while other.ranges.get(j).is_some_and(|r| r.1 < range_start) {
+                j += 1;
+            }
+            let mut start = Some(range_start);
+            for &(other_start, other_end) in &other.ranges[j..] {
+                let Some(current) = start else { break };
+                if other_start > end {
+                    break;
+                }
+                if other_start > current {
+                    ranges.push((current, other_start - 1));
+                }
+                start = other_end.checked_add(1).filter(|&s| s <= end);
+            }
+            if let Some(current) = start {
+                ranges.push((current, end));
+            }
+        }
+
+        Self { ranges }
+    }
+}
+
+impl FromIterator<RangeInclusive<u64>> for RangeSet {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<u64>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Parses a range such as `3-5`. Fails if the start is greater than the end.
+pub fn parse_range(input: &str) -> IResult<&str, RangeInclusive<u64>> {
+    map_opt(
+        separated_pair(unsigned, char('-'), unsigned),
+        |(start, end)| (start <= end).then_some(start..=end),
+    )
+    .parse(input)
+}
+
+/// Parses a list of ranges such as `3-5,10-14` separated by `separator` into a [`RangeSet`].
+pub fn parse_range_list<'a>(
+    separator: char,
+) -> impl Parser<&'a str, Output = RangeSet, Error = nom::error::Error<&'a str>> {
+    separated_list1(char(separator), parse_range).map(RangeSet::from_iter)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{RangeSet, parse_range, parse_range_list};
+    use crate::property::{check, int, vec};
+    use nom::Parser;
+
+    fn get_mock_set() -> RangeSet {
+        RangeSet::from_iter([10..=14, 3..=5, 16..=20, 12..=18])
+    }
+
+    #[test]
+    fn merges_on_insert() {
+        let set = get_mock_set();
+        assert_eq!(set.ranges().collect::<Vec<_>>(), vec![3..=5, 10..=20]);
+
+        let mut set = set;
+        set.insert(6..=9);
+        assert_eq!(set.ranges().collect::<Vec<_>>(), vec![3..=20]);
+        set.insert(u64::MAX - 1..=u64::MAX);
+        set.insert(0..=0);
+        assert_eq!(set.ranges().count(), 3);
+    }
+
+    #[test]
+    fn keeps_ranges_disjoint_and_sorted() {
+        let ranges = vec((int(0u64..=100), int(0u64..=10)), 0..=12);
+        check(&(ranges, int(0u64..=120)), |(ranges, probe)| {
+            let set = RangeSet::from_iter(ranges.iter().map(|&(start, len)| start..=start + len));
+            let merged: Vec<_> = set.ranges().collect();
+            let sorted = merged
+                .windows(2)
+                .all(|pair| pair[0].end() + 1 < *pair[1].start());
+            let covered = ranges
+                .iter()
+                .any(|&(start, len)| (start..=start + len).contains(probe));
+            sorted && set.contains(*probe) == covered
+        });
+    }
+
+    #[test]
+    fn answers_queries() {
+        let set = get_mock_set();
+        assert!(set.contains(3));
+        assert!(set.contains(17));
+        assert!(!set.contains(8));
+        assert!(!set.contains(21));
+        assert_eq!(set.total_len(), 14);
+        assert_eq!(RangeSet::from_iter([0..=u64::MAX]).total_len(), u64::MAX);
+        assert_eq!(set.gaps().collect::<Vec<_>>(), vec![6..=9]);
+    }
+
+    #[test]
+    fn computes_set_operations() {
+        let set = get_mock_set();
+        let other = RangeSet::from_iter([0..=3, 8..=11, 15..=15, 20..=30]);
+        assert_eq!(
+            set.intersection(&other).ranges().collect::<Vec<_>>(),
+            vec![3..=3, 10..=11, 15..=15, 20..=20]
+        );
+        assert_eq!(
+            set.difference(&other).ranges().collect::<Vec<_>>(),
+            vec![4..=5, 12..=14, 16..=19]
+        );
+        assert_eq!(
+            set.union(&other).ranges().collect::<Vec<_>>(),
+            vec![0..=5, 8..=30]
+        );
+        let full = RangeSet::from_iter([0..=u64::MAX]);
+        assert!(set.difference(&full).is_empty());
+        assert_eq!(full.difference(&set).total_len(), u64::MAX - 13);
+    }
+
+    #[test]
+    fn parses_range_lists() {
+        assert_eq!(parse_range("3-5"), Ok(("", 3..=5)));
+        assert!(parse_range("5-3").is_err());
+        let (rest, set) = parse_range_list(',').parse("11-22,95-115\n").unwrap();
+        assert_eq!(rest, "\n");
+        assert_eq!(set.total_len(), 12 + 21);
+        let (rest, set) = parse_range_list('\n').parse("3-5\n10-14\n\n1\n").unwrap();
+        assert_eq!(rest, "\n\n1\n");
+        assert_eq!(set.total_len(), 8);
+    }
+}
@@ -0,0 +1,226 @@
+use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::Day;
+
+static ANSWERS_FILE_PATH: &str = "./data/answers.json";
+
+/// Represents the known answers for a single day.
+#[derive(Clone, Debug)]
+pub struct Answer {
+    pub day: Day,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+/// Represents the known answers for a set of days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<Answer>,
+}
+
+impl Answers {
+    /// Dehydrate answers to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(ANSWERS_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from a JSON file. If not present, returns empty answers.
+    pub fn read_from_file() -> Self {
+        fs::read_to_string(ANSWERS_FILE_PATH)
+            .map_err(|x| x.to_string())
+            .and_then(Answers::try_from)
+            .unwrap_or_default()
+    }
+
+    /// Merge two sets of answers, overwriting parts of `self` with parts of `new` if present.
+    pub fn merge(&self, new: &Self) -> Self {
+        let mut data = self.data.clone();
+
+        for answer in &new.data {
+            match data.iter_mut().find(|a| a.day == answer.day) {
+                Some(existing) => {
+                    if answer.part_1.is_some() {
+                        existing.part_1.clone_from(&answer.part_1);
+                    }
+                    if answer.part_2.is_some() {
+                        existing.part_2.clone_from(&answer.part_2);
+                    }
+                }
+                None => data.push(answer.clone()),
+            }
+        }
+
+        data.sort_unstable_by_key(|a| a.day);
+        Answers { data }
+    }
+
+    /// Returns the known answer for a part of a day.
+    pub fn get(&self, day: Day, part: u8) -> Option<&str> {
+        let answer = self.data.iter().find(|a| a.day == day)?;
+        match part {
+            1 => answer.part_1.as_deref(),
+            2 => answer.part_2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Answer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+
+        for (key, answer) in [("part_1", &value.part_1), ("part_2", &value.part_2)] {
+            map.insert(
+                key.into(),
+                answer.clone().map_or(JsonValue::Null, JsonValue::String),
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answer.day to be a Day struct.")?;
+
+        let part_1 = json
+            .get("part_1")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_1 to be null or string.")?;
+
+        let part_2 = json
+            .get("part_2")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_2 to be null or string.")?;
+
+        Ok(Answer {
+            day,
+            part_1: part_1.cloned(),
+            part_2: part_2.cloned(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::day;
+
+    use super::{Answer, Answers};
+
+    fn get_mock_answers() -> Answers {
+        Answers {
+            data: vec![
+                Answer {
+                    day: day!(1),
+                    part_1: Some("42".into()),
+                    part_2: Some("1337".into()),
+                },
+                Answer {
+                    day: day!(3),
+                    part_1: Some("7".into()),
+                    part_2: None,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn handles_json_answers() {
+        let json = r#"{ "data": [{ "day": "02", "part_1": "12", "part_2": null }] }"#.to_string();
+        let answers = Answers::try_from(json).unwrap();
+        assert_eq!(answers.get(day!(2), 1), Some("12"));
+        assert_eq!(answers.get(day!(2), 2), None);
+        assert_eq!(answers.get(day!(1), 1), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_answers() {
+        let json = r#"{ "data": [{ "day": "26" }] }"#.to_string();
+        Answers::try_from(json).unwrap();
+    }
+
+    #[test]
+    fn merges_parts() {
+        let answers = get_mock_answers();
+        let other = Answers {
+            data: vec![
+                Answer {
+                    day: day!(3),
+                    part_1: None,
+                    part_2: Some("8".into()),
+                },
+                Answer {
+                    day: day!(2),
+                    part_1: Some("5".into()),
+                    part_2: None,
+                },
+            ],
+        };
+        let merged = answers.merge(&other);
+        assert_eq!(merged.data.len(), 3);
+        assert_eq!(merged.data[1].day, day!(2));
+        assert_eq!(merged.get(day!(3), 1), Some("7"));
+        assert_eq!(merged.get(day!(3), 2), Some("8"));
+        assert_eq!(merged.get(day!(1), 2), Some("1337"));
+    }
+}
@@ -0,0 +1,223 @@
+/// Module that renders timing information as a SVG bar chart.
+/// Each part gets its own horizontal bar, bar lengths use a logarithmic scale.
+use std::{fmt::Write, fs, io, time::Duration};
+
+use crate::template::run_multi::child_commands::parse_duration_nanos;
+use crate::template::timings::Timings;
+
+pub static CHART_FILE_PATH: &str = "./.assets/benchmarks.svg";
+
+const WIDTH: f64 = 800.0;
+const LABEL_WIDTH: f64 = 110.0;
+const VALUE_WIDTH: f64 = 90.0;
+const ROW_HEIGHT: f64 = 18.0;
+const BAR_HEIGHT: f64 = 12.0;
+const MARGIN: f64 = 10.0;
+const AXIS_HEIGHT: f64 = 20.0;
+
+const PART_COLORS: [&str; 2] = ["#ffd700", "#4fa3d1"];
+
+/// A bar of the chart, i.e. the runtime of a single part.
+struct Bar {
+    label: String,
+    part: u8,
+    time: String,
+    nanos: f64,
+}
+
+fn bars(timings: &Timings) -> Vec<Bar> {
+    timings
+        .data
+        .iter()
+        .flat_map(|timing| {
+            [(1, &timing.part_1), (2, &timing.part_2)]
+                .into_iter()
+                .filter_map(|(part, time)| {
+                    let time = time.as_ref()?;
+                    Some(Bar {
+                        label: format!("Day {} · Part {part}", timing.day.into_inner()),
+                        part,
+                        time: time.clone(),
+                        nanos: parse_duration_nanos(time)?,
+                    })
+                })
+        })
+        .collect()
+}
+
+/// Maps `nanos` onto `[0, 1]` on a logarithmic scale spanning the decades `lo..=hi`.
+fn log_position(nanos: f64, lo: i32, hi: i32) -> f64 {
+    let span = f64::from((hi - lo).max(1));
+    ((nanos.max(1.0).log10() - f64::from(lo)) / span).clamp(0.0, 1.0)
+}
+
+/// Renders `timings` as a SVG document.
+pub fn render(timings: &Timings) -> String {
+    let bars = bars(timings);
+
+    let plot_x = MARGIN + LABEL_WIDTH;
+    let plot_width = WIDTH - plot_x - VALUE_WIDTH - MARGIN;
+    #[allow(clippy::cast_precision_loss)]
+    let height = MARGIN * 2.0 + AXIS_HEIGHT + ROW_HEIGHT * bars.len().max(1) as f64;
+
+    let mut svg = String::new();
+
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{height}" viewBox="0 0 {WIDTH} {height}" font-family="monospace" font-size="11">"#
+    );
+    let _ = writeln!(
+        svg,
+        r##"<rect width="100%" height="100%" fill="#0f0f23"/>"##
+    );
+
+    if bars.is_empty() {
+        let _ = writeln!(
+            svg,
+            r##"<text x="{MARGIN}" y="{}" fill="#cccccc">No benchmarks stored yet.</text>"##,
+            MARGIN + ROW_HEIGHT
+        );
+        svg.push_str("</svg>\n");
+        return svg;
+    }
+
+    let (min, max) = bars.iter().fold((f64::MAX, f64::MIN), |(min, max), bar| {
+        (min.min(bar.nanos), max.max(bar.nanos))
+    });
+
+    #[allow(clippy::cast_possible_truncation)]
+    let lo = min.max(1.0).log10().floor() as i32;
+    #[allow(clippy::cast_possible_truncation)]
+    let hi = (max.max(1.0).log10().ceil() as i32).max(lo + 1);
+
+    // NOTE: one grid line per decade, labelled with the matching duration.
+    let axis_y = MARGIN + ROW_HEIGHT * bars.len() as f64;
+    for exponent in lo..=hi {
+        let x = plot_x + plot_width * log_position(10_f64.powi(exponent), lo, hi);
+        let label = format!(
+            "{:?}",
+            Duration::from_nanos(10_u64.pow(exponent.unsigned_abs()))
+        );
+        let _ = writeln!(
+            svg,
+            r##"<line x1="{x:.1}" y1="{MARGIN}" x2="{x:.1}" y2="{axis_y}" stroke="#333340"/>"##
+        );
+        let _ = writeln!(
+            svg,
+            r##"<text x="{x:.1}" y="{:.1}" fill="#999999" text-anchor="middle">{label}</text>"##,
+            axis_y + AXIS_HEIGHT * 0.75
+        );
+    }
+
+    for (i, bar) in bars.iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let y = MARGIN + ROW_HEIGHT * i as f64;
+        let bar_y = y + (ROW_HEIGHT - BAR_HEIGHT) / 2.0;
+        let text_y = y + ROW_HEIGHT * 0.7;
+        let bar_width = (plot_width * log_position(bar.nanos, lo, hi)).max(1.0);
+        let color = PART_COLORS[usize::from(bar.part - 1)];
+
+        let _ = writeln!(
+            svg,
+            r##"<text x="{:.1}" y="{text_y:.1}" fill="#cccccc" text-anchor="end">{}</text>"##,
+            plot_x - 6.0,
+            bar.label
+        );
+        let _ = writeln!(
+            svg,
+            r#"<rect x="{plot_x}" y="{bar_y:.1}" width="{bar_width:.1}" height="{BAR_HEIGHT}" fill="{color}"/>"#
+        );
+        let _ = writeln!(
+            svg,
+            r##"<text x="{:.1}" y="{text_y:.1}" fill="#cccccc">{}</text>"##,
+            plot_x + bar_width + 6.0,
+            bar.time
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Writes the chart for `timings` to [`CHART_FILE_PATH`].
+pub fn update(timings: &Timings) -> Result<(), io::Error> {
+    fs::write(CHART_FILE_PATH, render(timings))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{log_position, render};
+    use crate::{day, template::timings::Timing, template::timings::Timings};
+
+    fn get_mock_timings() -> Timings {
+        Timings {
+            data: vec![
+                Timing {
+                    day: day!(1),
+                    part_1: Some("120ns".into()),
+                    part_2: Some("3.2ms".into()),
+                    part_1_median: None,
+                    part_2_median: None,
+                    total_nanos: 3_200_120_f64,
+                    timed_out: false,
+                    parse: None,
+                    part_1_allocations: None,
+                    part_2_allocations: None,
+                    part_1_answer: None,
+                    part_2_answer: None,
+                },
+                Timing {
+                    day: day!(2),
+                    part_1: Some("45µs".into()),
+                    part_2: None,
+                    part_1_median: None,
+                    part_2_median: None,
+                    total_nanos: 45_000_f64,
+                    timed_out: false,
+                    parse: None,
+                    part_1_allocations: None,
+                    part_2_allocations: None,
+                    part_1_answer: None,
+                    part_2_answer: None,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn renders_one_bar_per_part() {
+        let svg = render(&get_mock_timings());
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>\n"));
+        // NOTE: one background rect plus one rect per solved part.
+        assert_eq!(svg.matches("<rect").count(), 4);
+        assert!(svg.contains("Day 1 · Part 2"));
+        assert!(svg.contains(">3.2ms</text>"));
+        assert!(!svg.contains("Day 2 · Part 2"));
+    }
+
+    #[test]
+    fn renders_decade_grid_lines() {
+        let svg = render(&get_mock_timings());
+        // NOTE: 120ns to 3.2ms spans the decades 100ns to 10ms.
+        assert_eq!(svg.matches("<line").count(), 6);
+        assert!(svg.contains(">100ns</text>"));
+        assert!(svg.contains(">10ms</text>"));
+    }
+
+    #[test]
+    fn renders_empty_timings() {
+        let svg = render(&Timings::default());
+        assert!(svg.contains("No benchmarks stored yet."));
+    }
+
+    #[test]
+    fn uses_logarithmic_scale() {
+        assert_eq!(log_position(100.0, 2, 6), 0.0);
+        assert_eq!(log_position(10_000.0, 2, 6), 0.5);
+        assert_eq!(log_position(1_000_000.0, 2, 6), 1.0);
+        assert_eq!(log_position(1e9, 2, 6), 1.0);
+    }
+}
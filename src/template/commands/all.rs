@@ -1,5 +1,31 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::process;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::answers::Answers;
+use crate::template::summary::{DaySummary, print_summary};
+use crate::template::{all_days, limits::Limits, run_multi::run_multi};
+
+pub fn handle(is_release: bool, limits: &Limits, store_answers: bool) {
+    let (_, summaries) = run_multi(&all_days().collect(), is_release, false, limits, false);
+
+    print_summary(&summaries);
+
+    if store_answers {
+        let answers = Answers {
+            data: summaries
+                .iter()
+                .map(DaySummary::answers)
+                .filter(|a| a.part_1.is_some() || a.part_2.is_some())
+                .collect(),
+        };
+
+        println!();
+        match Answers::read_from_file().merge(&answers).store_file() {
+            Ok(()) => println!("Stored answers."),
+            Err(e) => eprintln!("Failed to store answers: {e}"),
+        }
+    }
+
+    if summaries.iter().any(|s| s.has_failures()) {
+        process::exit(1);
+    }
 }
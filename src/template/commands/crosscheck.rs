@@ -0,0 +1,29 @@
+use std::process::{self, Command, Stdio};
+
+use crate::template::Day;
+
+pub fn handle(day: Day, cases: u64, size: usize) {
+    let cmd_args = vec![
+        "run".to_string(),
+        "--quiet".to_string(),
+        "--release".to_string(),
+        "--bin".to_string(),
+        day.to_string(),
+        "--".to_string(),
+        "--crosscheck".to_string(),
+        cases.to_string(),
+        size.to_string(),
+    ];
+
+    let mut cmd = Command::new("cargo")
+        .args(&cmd_args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .unwrap();
+
+    let status = cmd.wait().unwrap();
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
+}
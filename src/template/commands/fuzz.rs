@@ -0,0 +1,31 @@
+use std::process::{self, Command, Stdio};
+
+use crate::template::Day;
+
+/// Runs the fuzzer in the `fuzz` profile, a release build with overflow checks and debug assertions.
+pub fn handle(day: Day, iterations: u64, seed: u64) {
+    let cmd_args = vec![
+        "run".to_string(),
+        "--quiet".to_string(),
+        "--profile".to_string(),
+        "fuzz".to_string(),
+        "--bin".to_string(),
+        day.to_string(),
+        "--".to_string(),
+        "--fuzz".to_string(),
+        iterations.to_string(),
+        seed.to_string(),
+    ];
+
+    let mut cmd = Command::new("cargo")
+        .args(&cmd_args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .unwrap();
+
+    let status = cmd.wait().unwrap();
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
+}
@@ -0,0 +1,26 @@
+use std::process::{Command, Stdio};
+
+use crate::template::Day;
+
+pub fn handle(day: Day, seed: u64, size: usize) {
+    let cmd_args = vec![
+        "run".to_string(),
+        "--quiet".to_string(),
+        "--release".to_string(),
+        "--bin".to_string(),
+        day.to_string(),
+        "--".to_string(),
+        "--generate".to_string(),
+        seed.to_string(),
+        size.to_string(),
+    ];
+
+    let mut cmd = Command::new("cargo")
+        .args(&cmd_args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .unwrap();
+
+    cmd.wait().unwrap();
+}
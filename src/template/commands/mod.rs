@@ -1,5 +1,8 @@
 pub mod all;
+pub mod crosscheck;
 pub mod download;
+pub mod fuzz;
+pub mod generate;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
@@ -1,10 +1,21 @@
 use std::collections::HashSet;
 
-use crate::template::run_multi::run_multi;
+use crate::template::limits::Limits;
+use crate::template::run_multi::{add_allocations, run_multi};
 use crate::template::timings::Timings;
-use crate::template::{Day, all_days, readme_benchmarks};
+use crate::template::{
+    Day, OutputFormat, all_days, benchmark_chart, readme_benchmarks, timings_export,
+};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
+pub fn handle(
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    output_format: Option<OutputFormat>,
+    out: Option<String>,
+    limits: &Limits,
+    allocations: bool,
+) {
     let stored_timings = Timings::read_from_file();
 
     let days_to_run = day.map_or_else(
@@ -21,14 +32,37 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    // NOTE: timings exported to stdout can be piped elsewhere, so progress goes to stderr then.
+    let export_to_stdout = output_format.is_some() && out.is_none();
+    let (timings, _) = run_multi(&days_to_run, true, true, limits, export_to_stdout);
+    let mut timings = timings.unwrap();
+
+    if allocations {
+        add_allocations(&mut timings, limits, export_to_stdout);
+    }
+
+    let merged_timings = stored_timings.merge(&timings);
+
+    if let Some(output_format) = output_format {
+        if !export_to_stdout {
+            println!();
+        }
+        if let Err(e) = timings_export::export(&merged_timings, output_format, out.as_deref()) {
+            eprintln!("Failed to export benchmarks: {e}");
+        }
+    }
 
     if store {
-        let merged_timings = stored_timings.merge(&timings);
         merged_timings.store_file().unwrap();
 
-        println!();
+        eprintln!();
+        if let Err(e) = benchmark_chart::update(&merged_timings) {
+            eprintln!("Failed to store benchmark chart: {e}");
+        }
         match readme_benchmarks::update(merged_timings) {
+            Ok(()) if export_to_stdout => {
+                eprintln!("Stored updated benchmarks.");
+            }
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
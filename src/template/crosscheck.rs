@@ -0,0 +1,194 @@
+/// Compares solutions against slower reference solutions on example and generated inputs.
+use std::{
+    env,
+    fmt::{self, Display},
+    fs, process,
+};
+
+use crate::template::{ANSI_BOLD, ANSI_RESET, Day};
+
+/// A solution of one part with its answer converted to a string, so that parts of any type can be compared.
+pub type Solver<'a> = &'a dyn Fn(&str) -> Option<String>;
+
+/// A solution of one part and the reference solution it is checked against.
+pub struct Check<'a> {
+    pub part: u8,
+    pub solution: Solver<'a>,
+    pub reference: Solver<'a>,
+}
+
+impl Check<'_> {
+    fn mismatch(&self, input: &str) -> Option<(Option<String>, Option<String>)> {
+        let solution = (self.solution)(input);
+        let reference = (self.reference)(input);
+        (solution != reference).then_some((solution, reference))
+    }
+}
+
+/// Parse the `--crosscheck <cases> <size>` arguments passed by the `crosscheck` command.
+/// Returns [`None`] if the solution should be run instead.
+pub fn crosscheck_args() -> Option<(u64, usize)> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|x| x == "--crosscheck")?;
+
+    let cases = args.get(index + 1).and_then(|x| x.parse().ok());
+    let size = args.get(index + 2).and_then(|x| x.parse().ok());
+    let (Some(cases), Some(size)) = (cases, size) else {
+        eprintln!(
+            "Unexpected command-line input. Format: cargo crosscheck 1 --cases 100 --size 20"
+        );
+        process::exit(1);
+    };
+
+    Some((cases, size))
+}
+
+/// A check that failed, with the smallest failing input found.
+pub struct Failure {
+    pub part: u8,
+    /// Where the failing input came from, e.g. `example` or `seed 3, size 7`.
+    pub origin: String,
+    pub input: String,
+    pub solution: Option<String>,
+    pub reference: Option<String>,
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Part {}: {ANSI_BOLD}mismatch{ANSI_RESET} ({}), shrunk to {} lines:",
+            self.part,
+            self.origin,
+            self.input.lines().count()
+        )?;
+        writeln!(f, "{}", self.input)?;
+        writeln!(f, "  solution:  {:?}", self.solution)?;
+        writeln!(f, "  reference: {:?}", self.reference)
+    }
+}
+
+/// Runs every check on the example input and on `cases` generated inputs of the given `size`.
+/// Prints the smallest failing input found for each part and exits with an error if any check fails.
+pub fn run(
+    day: Day,
+    checks: &[Check],
+    generate: fn(u64, usize) -> String,
+    cases: u64,
+    size: usize,
+) {
+    if checks.is_empty() {
+        eprintln!(
+            "Day {day} has no reference solutions. Declare it with `solution!({}, reference)` \
+            and implement `part_one_reference` and `part_two_reference`.",
+            day.into_inner()
+        );
+        process::exit(1);
+    }
+
+    let example = read_example(day);
+    let mut failed = false;
+
+    for check in checks {
+        match find_failure(check, example.as_deref(), generate, cases, size) {
+            Some(failure) => {
+                failed = true;
+                print!("{failure}");
+            }
+            None => println!(
+                "Part {}: {ANSI_BOLD}ok{ANSI_RESET} (example and {cases} generated inputs)",
+                check.part
+            ),
+        }
+    }
+
+    if failed {
+        process::exit(1);
+    }
+}
+
+/// Runs the same checks as [`run`], but panics with the smallest failing inputs instead of exiting.
+/// Days declared with `solution!(N, reference)` call this from their tests through `crosscheck(cases, size)`.
+#[track_caller]
+pub fn assert_checks(
+    day: Day,
+    checks: &[Check],
+    generate: fn(u64, usize) -> String,
+    cases: u64,
+    size: usize,
+) {
+    let example = read_example(day);
+    let failures: Vec<String> = checks
+        .iter()
+        .filter_map(|check| find_failure(check, example.as_deref(), generate, cases, size))
+        .map(|failure| failure.to_string())
+        .collect();
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+fn read_example(day: Day) -> Option<String> {
+    fs::read_to_string(format!("data/examples/{day}.txt")).ok()
+}
+
+/// Checks the example and then the generated inputs, and shrinks the first one that fails.
+fn find_failure(
+    check: &Check,
+    example: Option<&str>,
+    generate: fn(u64, usize) -> String,
+    cases: u64,
+    size: usize,
+) -> Option<Failure> {
+    let (origin, input) = example
+        .filter(|input| check.mismatch(input).is_some())
+        .map(|input| (String::from("example"), input.to_string()))
+        .or_else(|| {
+            (0..cases).find_map(|seed| {
+                let input = generate(seed, size);
+                check.mismatch(&input)?;
+                // NOTE: the smallest size that still fails for this seed is a good start for shrinking.
+                let (size, input) = (0..size)
+                    .map(|size| (size, generate(seed, size)))
+                    .find(|(_, input)| check.mismatch(input).is_some())
+                    .unwrap_or((size, input));
+                Some((format!("seed {seed}, size {size}"), input))
+            })
+        })?;
+
+    let input = shrink(check, &input);
+    let (solution, reference) = check.mismatch(&input).unwrap_or_default();
+    Some(Failure {
+        part: check.part,
+        origin,
+        input,
+        solution,
+        reference,
+    })
+}
+
+/// Removes chunks of lines from `input`, halving the chunk size, as long as the check still fails.
+fn shrink(check: &Check, input: &str) -> String {
+    let join = |lines: &[&str]| {
+        lines
+            .iter()
+            .map(|line| format!("{line}\n"))
+            .collect::<String>()
+    };
+    let mut lines: Vec<&str> = input.lines().collect();
+    let mut chunk = lines.len() / 2;
+
+    while chunk > 0 {
+        let mut start = 0;
+        while start < lines.len() {
+            let end = (start + chunk).min(lines.len());
+            let candidate = [&lines[..start], &lines[end..]].concat();
+            if check.mismatch(&join(&candidate)).is_some() {
+                lines = candidate;
+            } else {
+                start += chunk;
+            }
+        }
+        chunk /= 2;
+    }
+
+    join(&lines)
+}
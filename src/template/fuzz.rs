@@ -0,0 +1,260 @@
+/// Runs solutions on mutated inputs to find panics, overflows and hangs on malformed input.
+use std::{
+    env, fs,
+    panic::{self, PanicHookInfo},
+    process,
+    sync::{Mutex, mpsc},
+    thread,
+    time::Duration,
+};
+
+use crate::random::Rng;
+use crate::template::{ANSI_BOLD, ANSI_RESET, Day};
+
+/// Inputs are truncated to this many bytes.
+const MAX_LEN: usize = 4096;
+
+/// A part that takes longer than this on a fuzzed input is considered stuck in an infinite loop.
+const TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Sizes passed to `generate` to seed the corpus.
+const GENERATED_SIZES: [usize; 6] = [0, 1, 2, 5, 10, 20];
+
+/// Byte sequences that often reach edge cases of puzzle parsers.
+const TOKENS: [&[u8]; 12] = [
+    b"0",
+    b"-",
+    b",",
+    b"\n",
+    b"\n\n",
+    b" ",
+    b"64",
+    b"65",
+    b"4294967296",
+    b"18446744073709551615",
+    b"99999999999999999999999",
+    b"-9223372036854775808",
+];
+
+/// The message and location of the last panic, recorded instead of printed while fuzzing.
+static LAST_PANIC: Mutex<Option<String>> = Mutex::new(None);
+
+/// A part to fuzz. The answer is discarded; only panics and hangs count as failures.
+pub struct Target<'a> {
+    pub part: u8,
+    pub run: &'a (dyn Fn(&str) + Sync),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Outcome {
+    Ok,
+    Panic(String),
+    Timeout,
+}
+
+/// Parse the `--fuzz <iterations> <seed>` arguments passed by the `fuzz` command.
+/// Returns [`None`] if the solution should be run instead.
+pub fn fuzz_args() -> Option<(u64, u64)> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|x| x == "--fuzz")?;
+
+    let iterations = args.get(index + 1).and_then(|x| x.parse().ok());
+    let seed = args.get(index + 2).and_then(|x| x.parse().ok());
+    let (Some(iterations), Some(seed)) = (iterations, seed) else {
+        eprintln!(
+            "Unexpected command-line input. Format: cargo fuzz 1 --iterations 10000 --seed 0"
+        );
+        process::exit(1);
+    };
+
+    Some((iterations, seed))
+}
+
+/// Feeds `iterations` mutated inputs to every target, starting from the examples and generated inputs.
+/// Prints the smallest input found that makes a part panic or hang and exits with an error if any part fails.
+pub fn run(
+    day: Day,
+    targets: &[Target],
+    generate: fn(u64, usize) -> String,
+    iterations: u64,
+    seed: u64,
+) {
+    let mut corpus: Vec<Vec<u8>> = examples(day);
+    corpus.extend(
+        GENERATED_SIZES
+            .iter()
+            .map(|&size| generate(seed, size).into_bytes()),
+    );
+
+    panic::set_hook(Box::new(record_panic));
+
+    let mut rng = Rng::new(seed);
+    let mut failed = vec![false; targets.len()];
+    for _ in 0..iterations {
+        let mut input = rng.choose(&corpus).cloned().unwrap_or_default();
+        for _ in 0..=rng.below(4) {
+            mutate(&mut rng, &mut input, &corpus);
+        }
+        input.truncate(MAX_LEN);
+
+        let mut passed = true;
+        for (target, failed) in targets.iter().zip(failed.iter_mut()) {
+            if *failed {
+                continue;
+            }
+            let outcome = execute(target, &input);
+            if outcome != Outcome::Ok {
+                report(target, input.clone(), outcome);
+                *failed = true;
+                passed = false;
+            }
+        }
+
+        // NOTE: keeping some inputs that pass lets mutations accumulate over several iterations.
+        if passed && corpus.len() < 256 && rng.chance(0.1) {
+            corpus.push(input);
+        }
+        if failed.iter().all(|&f| f) {
+            break;
+        }
+    }
+
+    let _ = panic::take_hook();
+
+    for (target, &failed) in targets.iter().zip(&failed) {
+        if !failed {
+            println!(
+                "Part {}: {ANSI_BOLD}ok{ANSI_RESET} ({iterations} inputs)",
+                target.part
+            );
+        }
+    }
+    if failed.contains(&true) {
+        process::exit(1);
+    }
+}
+
+fn examples(day: Day) -> Vec<Vec<u8>> {
+    let mut examples: Vec<Vec<u8>> = fs::read_dir("data/examples")
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| {
+            entry
+                .file_name()
+                .to_string_lossy()
+                .starts_with(&day.to_string())
+        })
+        .filter_map(|entry| fs::read(entry.path()).ok())
+        .collect();
+    examples.sort();
+    examples
+}
+
+fn record_panic(info: &PanicHookInfo) {
+    let message = info
+        .payload()
+        .downcast_ref::<&str>()
+        .map(ToString::to_string)
+        .or_else(|| info.payload().downcast_ref::<String>().cloned())
+        .unwrap_or_default();
+    let location = info
+        .location()
+        .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()))
+        .unwrap_or_default();
+    *LAST_PANIC.lock().unwrap_or_else(|e| e.into_inner()) =
+        Some(format!("{message} at {location}"));
+}
+
+/// Runs a target on a worker thread, so that panics are caught and a hang can be detected.
+fn execute(target: &Target, input: &[u8]) -> Outcome {
+    let input = String::from_utf8_lossy(input);
+    thread::scope(|scope| {
+        let (sender, receiver) = mpsc::channel();
+        let input = &input;
+        let worker = scope.spawn(move || {
+            (target.run)(input);
+            let _ = sender.send(());
+        });
+        match receiver.recv_timeout(TIMEOUT) {
+            // NOTE: joining marks a panic as handled, otherwise the scope would panic on exit.
+            Ok(()) | Err(mpsc::RecvTimeoutError::Disconnected) => match worker.join() {
+                Ok(()) => Outcome::Ok,
+                Err(_) => Outcome::Panic(
+                    LAST_PANIC
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .take()
+                        .unwrap_or_default(),
+                ),
+            },
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                // NOTE: a stuck thread cannot be stopped, and the scope would wait for it forever.
+                report(target, input.as_bytes().to_vec(), Outcome::Timeout);
+                process::exit(1);
+            }
+        }
+    })
+}
+
+fn report(target: &Target, input: Vec<u8>, outcome: Outcome) {
+    let (input, description) = match outcome {
+        Outcome::Panic(message) => (shrink(target, input), format!("panicked: {message}")),
+        // NOTE: every failing attempt to shrink a hang would take the full timeout.
+        _ => (input, format!("did not finish within {TIMEOUT:?}")),
+    };
+    println!("Part {}: {ANSI_BOLD}{description}{ANSI_RESET}", target.part);
+    println!("  input: {:?}", String::from_utf8_lossy(&input));
+}
+
+/// Removes chunks of bytes from `input`, halving the chunk size, as long as the target still panics.
+fn shrink(target: &Target, mut input: Vec<u8>) -> Vec<u8> {
+    let mut chunk = input.len() / 2;
+    while chunk > 0 {
+        let mut start = 0;
+        while start < input.len() {
+            let end = (start + chunk).min(input.len());
+            let candidate = [&input[..start], &input[end..]].concat();
+            if matches!(execute(target, &candidate), Outcome::Panic(_)) {
+                input = candidate;
+            } else {
+                start += chunk;
+            }
+        }
+        chunk /= 2;
+    }
+    input
+}
+
+fn mutate(rng: &mut Rng, input: &mut Vec<u8>, corpus: &[Vec<u8>]) {
+    let position = rng.index(input.len() + 1);
+    match rng.below(6) {
+        0 if position < input.len() => input[position] = rng.below(256) as u8,
+        1 => {
+            let token = rng.choose(&TOKENS).copied().unwrap_or_default();
+            input.splice(position..position, token.iter().copied());
+        }
+        2 if position < input.len() => {
+            let end = position + 1 + rng.index((input.len() - position).min(16));
+            input.drain(position..end);
+        }
+        3 if position < input.len() => {
+            let end = position + 1 + rng.index((input.len() - position).min(64));
+            let copy = input[position..end].to_vec();
+            let target = rng.index(input.len() + 1);
+            input.splice(target..target, copy);
+        }
+        4 => {
+            let other = rng.choose(corpus).cloned().unwrap_or_default();
+            let from = rng.index(other.len() + 1);
+            input.truncate(position);
+            input.extend_from_slice(&other[from..]);
+        }
+        _ => {
+            let byte = *rng
+                .choose(b"0123456789 ,-:\n.#@^SLRx[](){}")
+                .unwrap_or(&b'0');
+            input.insert(position, byte);
+        }
+    }
+}
@@ -0,0 +1,100 @@
+/// Resource limits applied to solution binaries run by `cargo all` and `cargo time`.
+use std::{error::Error, fmt::Display, time::Duration};
+
+/// Limits for a single solution run.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Limits {
+    /// Wall-clock time after which the solution is killed.
+    pub timeout: Option<Duration>,
+    /// Address space ceiling in bytes. Only enforced on unix systems.
+    pub memory: Option<u64>,
+}
+
+/// Parses a timeout such as `30s`, `500ms` or `2m`. Plain numbers are treated as seconds.
+pub fn parse_timeout(s: &str) -> Result<Duration, LimitFromStrError> {
+    let (value, unit) = split_unit(s);
+    let value: f64 = value.parse().map_err(|_| LimitFromStrError::Timeout)?;
+
+    let secs = match unit {
+        "ms" => value / 1000_f64,
+        "" | "s" => value,
+        "m" => value * 60_f64,
+        "h" => value * 3600_f64,
+        _ => return Err(LimitFromStrError::Timeout),
+    };
+
+    Duration::try_from_secs_f64(secs).map_err(|_| LimitFromStrError::Timeout)
+}
+
+/// Parses a memory size such as `512M` or `2G`. Plain numbers are treated as bytes.
+pub fn parse_memory(s: &str) -> Result<u64, LimitFromStrError> {
+    let (value, unit) = split_unit(s);
+    let value: u64 = value.parse().map_err(|_| LimitFromStrError::Memory)?;
+
+    let factor: u64 = match unit.to_ascii_uppercase().as_str() {
+        "" | "B" => 1,
+        "K" | "KB" => 1 << 10,
+        "M" | "MB" => 1 << 20,
+        "G" | "GB" => 1 << 30,
+        _ => return Err(LimitFromStrError::Memory),
+    };
+
+    value.checked_mul(factor).ok_or(LimitFromStrError::Memory)
+}
+
+fn split_unit(s: &str) -> (&str, &str) {
+    let s = s.trim();
+    let pos = s
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(s.len());
+    s.split_at(pos)
+}
+
+/// An error which can be returned when parsing a [`Limits`] value.
+#[derive(Debug)]
+pub enum LimitFromStrError {
+    Timeout,
+    Memory,
+}
+
+impl Error for LimitFromStrError {}
+
+impl Display for LimitFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LimitFromStrError::Timeout => {
+                f.write_str("expecting a timeout such as `30s`, `500ms` or `2m`")
+            }
+            LimitFromStrError::Memory => {
+                f.write_str("expecting a memory size such as `512M` or `2G`")
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_memory, parse_timeout};
+    use std::time::Duration;
+
+    #[test]
+    fn parses_timeouts() {
+        assert_eq!(parse_timeout("30s").unwrap(), Duration::from_secs(30));
+        assert_eq!(parse_timeout("30").unwrap(), Duration::from_secs(30));
+        assert_eq!(parse_timeout("500ms").unwrap(), Duration::from_millis(500));
+        assert_eq!(parse_timeout("1.5m").unwrap(), Duration::from_secs(90));
+        assert!(parse_timeout("-1s").is_err());
+        assert!(parse_timeout("10 parsecs").is_err());
+    }
+
+    #[test]
+    fn parses_memory_sizes() {
+        assert_eq!(parse_memory("1024").unwrap(), 1024);
+        assert_eq!(parse_memory("512M").unwrap(), 512 * 1024 * 1024);
+        assert_eq!(parse_memory("2g").unwrap(), 2 * 1024 * 1024 * 1024);
+        assert!(parse_memory("1.5G").is_err());
+        assert!(parse_memory("lots").is_err());
+    }
+}
@@ -2,14 +2,22 @@ use std::{env, fs};
 
 pub mod aoc_cli;
 pub mod commands;
+pub mod crosscheck;
+pub mod fuzz;
+pub mod limits;
 pub mod runner;
 
 pub use day::*;
+pub use timings_export::OutputFormat;
 
+mod answers;
+mod benchmark_chart;
 mod day;
 mod readme_benchmarks;
 mod run_multi;
+mod summary;
 mod timings;
+mod timings_export;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
@@ -39,6 +47,19 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// Passing `shared` as second parameter runs `part_one_shared` and `part_two_shared` instead.
+/// Both are handed the state returned by `parse_shared`, which runs once and is timed separately.
+///
+/// Passing `reference` as second parameter also registers `part_one_reference` and `part_two_reference`,
+/// straightforward solutions that the `crosscheck` command compares `part_one` and `part_two` against.
+/// It also defines `crosscheck(cases, size)` for tests, which runs the same comparison and panics on a mismatch.
+///
+/// Every day also defines `generate(seed, size) -> String`, which the `generate` command uses to print a
+/// random input instead of running the solution.
+///
+/// The `fuzz` command runs every part on mutations of the examples and generated inputs, and reports inputs
+/// that make a part panic or hang.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
@@ -50,19 +71,97 @@ macro_rules! solution {
     ($day:expr, 2) => {
         $crate::solution!(@impl $day, [part_two, 2]);
     };
+    ($day:expr, reference) => {
+        $crate::solution!(
+            @impl $day, [part_one, 1] [part_two, 2];
+            [part_one, part_one_reference, 1] [part_two, part_two_reference, 2]
+        );
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
-        /// The current day.
-        const DAY: $crate::template::Day = $crate::day!($day);
+        /// Compares both parts with their reference solutions like `cargo crosscheck`, panicking on a mismatch.
+        #[cfg(test)]
+        fn crosscheck(cases: u64, size: usize) {
+            let checks = $crate::solution!(
+                @checks [part_one, part_one_reference, 1] [part_two, part_two_reference, 2]
+            );
+            $crate::template::crosscheck::assert_checks(DAY, &checks, generate, cases, size);
+        }
+    };
+    ($day:expr, shared) => {
+        $crate::solution!(@header $day);
 
-        #[cfg(feature = "dhat-heap")]
-        #[global_allocator]
-        static ALLOC: dhat::Alloc = dhat::Alloc;
+        fn main() {
+            use $crate::template::runner::*;
+            $crate::solution!(@commands [
+                (1, |input: &str| {
+                    let _ = parse_shared(input).as_ref().and_then(part_one_shared);
+                })
+                (2, |input: &str| {
+                    let _ = parse_shared(input).as_ref().and_then(part_two_shared);
+                })
+            ]);
+            let input = $crate::template::read_file("inputs", DAY);
+            run_shared(parse_shared, part_one_shared, part_two_shared, &input, DAY);
+        }
+    };
+
+    (
+        @impl $day:expr, $( [$func:expr, $part:expr] )*
+        $( ; $( [$solution:expr, $reference:expr, $check_part:expr] )* )?
+    ) => {
+        $crate::solution!(@header $day);
 
         fn main() {
             use $crate::template::runner::*;
+            $crate::solution!(
+                @commands [$( ($part, |input: &str| {
+                    let _ = $func(input);
+                }) )*]
+                $( $( [$solution, $reference, $check_part] )* )?
+            );
             let input = $crate::template::read_file("inputs", DAY);
             $( run_part($func, &input, DAY, $part); )*
         }
     };
+
+    (
+        @commands [$( ($target_part:expr, $target:expr) )*]
+        $( [$solution:expr, $reference:expr, $part:expr] )*
+    ) => {
+        if let Some((seed, size)) = generate_args() {
+            print!("{}", generate(seed, size));
+            return;
+        }
+
+        if let Some((cases, size)) = $crate::template::crosscheck::crosscheck_args() {
+            let checks = $crate::solution!(@checks $( [$solution, $reference, $part] )*);
+            $crate::template::crosscheck::run(DAY, &checks, generate, cases, size);
+            return;
+        }
+
+        if let Some((iterations, seed)) = $crate::template::fuzz::fuzz_args() {
+            use $crate::template::fuzz::{run, Target};
+            let targets = [$( Target { part: $target_part, run: &$target }, )*];
+            run(DAY, &targets, generate, iterations, seed);
+            return;
+        }
+    };
+
+    (@checks $( [$solution:expr, $reference:expr, $part:expr] )*) => {
+        [$(
+            $crate::template::crosscheck::Check {
+                part: $part,
+                solution: &|input: &str| $solution(input).map(|r| r.to_string()),
+                reference: &|input: &str| $reference(input).map(|r| r.to_string()),
+            },
+        )*]
+    };
+
+    (@header $day:expr) => {
+        /// The current day.
+        const DAY: $crate::template::Day = $crate::day!($day);
+
+        #[cfg(feature = "dhat-heap")]
+        #[global_allocator]
+        static ALLOC: dhat::Alloc = dhat::Alloc;
+    };
 }
@@ -1,11 +1,24 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+///
+/// The readme can contain several tables, each one delimited by a pair of identical markers or
+/// marked by a single marker that is expanded into a pair.
+/// Markers can carry an optional name and layout options, e.g.:
+/// `<!--- benchmarking table summary columns=day,total,bar sort=time chart=off --->`.
+///
+/// The status column compares the answers of the timed run against the stored answers.
+use std::{fs, io, str::FromStr};
 
 use crate::template::Day;
-use crate::template::timings::Timings;
+use crate::template::answers::Answers;
+use crate::template::benchmark_chart::CHART_FILE_PATH;
+use crate::template::timings::{Timing, Timings};
 
-static MARKER: &str = "<!--- benchmarking table --->";
+static MARKER_PREFIX: &str = "<!--- benchmarking table";
+static MARKER_SUFFIX: &str = "--->";
+
+/// Width of the bar chart column, in characters.
+const BAR_WIDTH: usize = 20;
 
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -21,70 +34,318 @@ impl From<std::io::Error> for Error {
 }
 
 pub struct TablePosition {
+    marker: String,
     pos_start: usize,
     pos_end: usize,
 }
 
+/// A column that can be shown in a benchmark table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Column {
+    Day,
+    Part1,
+    Part2,
+    Median1,
+    Median2,
+    Parse,
+    Allocations1,
+    Allocations2,
+    Total,
+    Status,
+    Bar,
+}
+
+impl Column {
+    fn title(self) -> &'static str {
+        match self {
+            Column::Day => "Day",
+            Column::Part1 => "Part 1",
+            Column::Part2 => "Part 2",
+            Column::Median1 => "Part 1 (median)",
+            Column::Median2 => "Part 2 (median)",
+            Column::Parse => "Parse",
+            Column::Allocations1 => "Part 1 (allocations)",
+            Column::Allocations2 => "Part 2 (allocations)",
+            Column::Total => "Total",
+            Column::Status => "Status",
+            Column::Bar => "Total (relative)",
+        }
+    }
+
+    fn cell(self, timing: &Timing, max_nanos: f64, answers: &Answers) -> String {
+        let missing = if timing.timed_out { "timeout" } else { "-" };
+        let code = |value: &Option<String>| format!("`{}`", value.as_deref().unwrap_or(missing));
+        let count = |value: Option<u64>| value.map_or_else(|| "`-`".into(), |x| format!("`{x}`"));
+        let status = |part: u8, time: &Option<String>, answer: &Option<String>| {
+            match (time, answer.as_deref(), answers.get(timing.day, part)) {
+                (None, _, _) if timing.timed_out => "⌛",
+                (None, _, _) => "✖",
+                (Some(_), Some(answer), Some(expected)) if answer == expected => "⭐",
+                (Some(_), Some(_), Some(_)) => "✘",
+                // NOTE: without a stored answer, a finished part cannot be told apart from a wrong one.
+                (Some(_), _, _) => "❔",
+            }
+        };
+
+        match self {
+            Column::Day => format!(
+                "[Day {}]({})",
+                timing.day.into_inner(),
+                get_path_for_bin(timing.day)
+            ),
+            Column::Part1 => code(&timing.part_1),
+            Column::Part2 => code(&timing.part_2),
+            Column::Median1 => code(&timing.part_1_median),
+            Column::Median2 => code(&timing.part_2_median),
+            // NOTE: only days that parse once for both parts time the parsing separately.
+            Column::Parse => format!("`{}`", timing.parse.as_deref().unwrap_or("-")),
+            Column::Allocations1 => count(timing.part_1_allocations),
+            Column::Allocations2 => count(timing.part_2_allocations),
+            Column::Total => format!("`{:.2}ms`", timing.total_nanos / 1_000_000_f64),
+            Column::Status => format!(
+                "{} {}",
+                status(1, &timing.part_1, &timing.part_1_answer),
+                status(2, &timing.part_2, &timing.part_2_answer)
+            ),
+            Column::Bar => bar(timing.total_nanos, max_nanos),
+        }
+    }
+}
+
+impl FromStr for Column {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "day" => Ok(Column::Day),
+            "part_1" => Ok(Column::Part1),
+            "part_2" => Ok(Column::Part2),
+            "median_1" => Ok(Column::Median1),
+            "median_2" => Ok(Column::Median2),
+            "parse" => Ok(Column::Parse),
+            "allocations_1" => Ok(Column::Allocations1),
+            "allocations_2" => Ok(Column::Allocations2),
+            "total" => Ok(Column::Total),
+            "status" => Ok(Column::Status),
+            "bar" => Ok(Column::Bar),
+            _ => Err(Error::Parser(format!("unknown table column `{s}`."))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SortOrder {
+    /// Ascending by day.
+    Day,
+    /// Descending by total time, i.e. slowest day first.
+    Time,
+}
+
+impl FromStr for SortOrder {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "day" => Ok(SortOrder::Day),
+            "time" => Ok(SortOrder::Time),
+            _ => Err(Error::Parser(format!("unknown table sort order `{s}`."))),
+        }
+    }
+}
+
+/// Layout of a single benchmark table, as configured by its marker.
+#[derive(Debug)]
+struct TableLayout {
+    name: Option<String>,
+    columns: Vec<Column>,
+    sort: SortOrder,
+    /// Whether the SVG benchmark chart is embedded below the table.
+    chart: bool,
+}
+
+impl Default for TableLayout {
+    fn default() -> Self {
+        Self {
+            name: None,
+            columns: vec![Column::Day, Column::Part1, Column::Part2],
+            sort: SortOrder::Day,
+            chart: true,
+        }
+    }
+}
+
+impl TableLayout {
+    /// Parses the options between the marker prefix and suffix, e.g. `summary columns=day,total chart=off`.
+    fn from_marker(marker: &str) -> Result<Self, Error> {
+        let options = marker
+            .strip_prefix(MARKER_PREFIX)
+            .and_then(|s| s.strip_suffix(MARKER_SUFFIX))
+            .ok_or_else(|| Error::Parser(format!("malformed marker `{marker}`.")))?;
+
+        let mut layout = TableLayout::default();
+
+        for option in options.split_whitespace() {
+            match option.split_once('=') {
+                Some(("columns", columns)) => {
+                    layout.columns = columns
+                        .split(',')
+                        .map(Column::from_str)
+                        .collect::<Result<_, _>>()?;
+                }
+                Some(("sort", sort)) => layout.sort = sort.parse()?,
+                Some(("chart", "on")) => layout.chart = true,
+                Some(("chart", "off")) => layout.chart = false,
+                Some((key, _)) => {
+                    return Err(Error::Parser(format!("unknown table option `{key}`.")));
+                }
+                None => layout.name = Some(option.into()),
+            }
+        }
+
+        Ok(layout)
+    }
+}
+
 #[must_use]
 pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+fn locate_tables(readme: &str) -> Result<Vec<TablePosition>, Error> {
+    let mut markers: Vec<(&str, Vec<usize>)> = vec![];
 
-    if matches.len() > 2 {
-        return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
-        ));
+    for (pos, _) in readme.match_indices(MARKER_PREFIX) {
+        let len = readme[pos..]
+            .find(MARKER_SUFFIX)
+            .map(|i| i + MARKER_SUFFIX.len())
+            .ok_or_else(|| Error::Parser("Could not find end of marker.".into()))?;
+        let marker = &readme[pos..pos + len];
+
+        match markers.iter_mut().find(|(m, _)| *m == marker) {
+            Some((_, positions)) => positions.push(pos),
+            None => markers.push((marker, vec![pos])),
+        }
     }
 
-    let pos_start = matches
-        .first()
-        .map(|m| m.0)
-        .ok_or_else(|| Error::Parser("Could not find table start position.".into()))?;
+    if markers.is_empty() {
+        return Err(Error::Parser("Could not find table start position.".into()));
+    }
+
+    markers
+        .into_iter()
+        .map(|(marker, positions)| match positions[..] {
+            [pos_start, pos_end] => Ok(TablePosition {
+                marker: marker.into(),
+                pos_start,
+                pos_end: pos_end + marker.len(),
+            }),
+            // NOTE: a lone marker, e.g. in a fresh readme, is replaced by a table enclosed in two markers.
+            [pos] => Ok(TablePosition {
+                marker: marker.into(),
+                pos_start: pos,
+                pos_end: pos + marker.len(),
+            }),
+            _ => Err(Error::Parser(format!(
+                "{marker}: too many occurences of marker in README."
+            ))),
+        })
+        .collect()
+}
 
-    let pos_end = matches
-        .last()
-        .map(|m| m.0 + m.1.len())
-        .ok_or_else(|| Error::Parser("Could not find table end position.".into()))?;
+fn bar(nanos: f64, max_nanos: f64) -> String {
+    const PARTIAL_BLOCKS: [char; 7] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉'];
 
-    Ok(TablePosition { pos_start, pos_end })
+    if nanos <= 0_f64 || max_nanos <= 0_f64 {
+        return String::new();
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let eighths = ((nanos / max_nanos * (BAR_WIDTH * 8) as f64).round() as usize).max(1);
+
+    let mut bar = "█".repeat(eighths / 8);
+    if let Some(partial) = (eighths % 8).checked_sub(1) {
+        bar.push(PARTIAL_BLOCKS[partial]);
+    }
+    bar
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+fn construct_table(
+    prefix: &str,
+    marker: &str,
+    layout: &TableLayout,
+    timings: &Timings,
+    answers: &Answers,
+    total_millis: f64,
+) -> String {
+    let header = match &layout.name {
+        Some(name) => format!("{prefix} Benchmarks ({name})"),
+        None => format!("{prefix} Benchmarks"),
+    };
+
+    let titles: Vec<_> = layout.columns.iter().map(|c| c.title()).collect();
+    let alignments = vec![":---:"; layout.columns.len()];
 
     let mut lines: Vec<String> = vec![
-        MARKER.into(),
+        marker.into(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        format!("| {} |", titles.join(" | ")),
+        format!("| {} |", alignments.join(" | ")),
     ];
 
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+    let mut data: Vec<&Timing> = timings.data.iter().collect();
+    match layout.sort {
+        SortOrder::Day => data.sort_by_key(|t| t.day),
+        SortOrder::Time => data.sort_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos)),
+    }
+
+    let max_nanos = data.iter().map(|t| t.total_nanos).fold(0_f64, f64::max);
+
+    for timing in data {
+        let cells: Vec<_> = layout
+            .columns
+            .iter()
+            .map(|c| c.cell(timing, max_nanos, answers))
+            .collect();
+        lines.push(format!("| {} |", cells.join(" | ")));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+
+    if layout.chart {
+        lines.push(String::new());
+        lines.push(format!("![Benchmark chart]({CHART_FILE_PATH})"));
+    }
+
+    lines.push(marker.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
-    s.replace_range(positions.pos_start..positions.pos_end, &table);
+fn update_content(
+    s: &mut String,
+    timings: Timings,
+    answers: &Answers,
+    total_millis: f64,
+) -> Result<(), Error> {
+    let mut positions = locate_tables(s)?;
+    positions.sort_by_key(|p| p.pos_start);
+
+    // NOTE: replace back to front so that earlier positions stay valid.
+    for position in positions.iter().rev() {
+        let layout = TableLayout::from_marker(&position.marker)?;
+        let table = construct_table(
+            "##",
+            &position.marker,
+            &layout,
+            &timings,
+            answers,
+            total_millis,
+        );
+        s.replace_range(position.pos_start..position.pos_end, &table);
+    }
+
     Ok(())
 }
 
@@ -92,15 +353,20 @@ pub fn update(timings: Timings) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    let answers = Answers::read_from_file();
+    update_content(&mut readme, timings, &answers, total_millis)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{MARKER, update_content};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{bar, update_content};
+    use crate::day;
+    use crate::template::answers::{Answer, Answers};
+    use crate::template::timings::{Timing, Timings};
+
+    const MARKER: &str = "<!--- benchmarking table --->";
 
     fn get_mock_timings() -> Timings {
         Timings {
@@ -109,19 +375,43 @@ mod tests {
                     day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    part_1_median: None,
+                    part_2_median: None,
                     total_nanos: 3e+10,
+                    timed_out: false,
+                    parse: None,
+                    part_1_allocations: None,
+                    part_2_allocations: None,
+                    part_1_answer: None,
+                    part_2_answer: None,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    part_1_median: None,
+                    part_2_median: None,
                     total_nanos: 7e+10,
+                    timed_out: false,
+                    parse: None,
+                    part_1_allocations: None,
+                    part_2_allocations: None,
+                    part_1_answer: None,
+                    part_2_answer: None,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
+                    part_1_median: None,
+                    part_2_median: None,
                     total_nanos: 9e+10,
+                    timed_out: false,
+                    parse: None,
+                    part_1_allocations: None,
+                    part_2_allocations: None,
+                    part_1_answer: None,
+                    part_2_answer: None,
                 },
             ],
         }
@@ -131,28 +421,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), &Answers::default(), 190.0).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), &Answers::default(), 190.0).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), &Answers::default(), 190.0).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), &Answers::default(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), &Answers::default(), 190.0).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,7 +450,7 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), &Answers::default(), 190.0).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -168,16 +458,155 @@ mod tests {
             "## Benchmarks",
             "",
             "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
+            "| :---: | :---: | :---: |",
             "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
             "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
             "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
             "",
             "**Total: 190.00ms**",
+            "",
+            "![Benchmark chart](./.assets/benchmarks.svg)",
             "<!--- benchmarking table --->",
             "baz",
         ]
         .join("\n");
         assert_eq!(s, expected);
     }
+    #[test]
+    fn updates_named_tables() {
+        let summary =
+            "<!--- benchmarking table summary columns=day,total,bar sort=time chart=off --->";
+        let detail = "<!--- benchmarking table detail columns=day,part_1,median_1,status --->";
+        let mut s = format!("foo\n{summary}\n{summary}\nbar\n{detail}{detail}\nbaz");
+        update_content(&mut s, get_mock_timings(), &Answers::default(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), &Answers::default(), 190.0).unwrap();
+        let expected = [
+            "foo",
+            summary,
+            "## Benchmarks (summary)",
+            "",
+            "| Day | Total | Total (relative) |",
+            "| :---: | :---: | :---: |",
+            "| [Day 4](./src/bin/04.rs) | `90000.00ms` | ████████████████████ |",
+            "| [Day 2](./src/bin/02.rs) | `70000.00ms` | ███████████████▌ |",
+            "| [Day 1](./src/bin/01.rs) | `30000.00ms` | ██████▋ |",
+            "",
+            "**Total: 190.00ms**",
+            summary,
+            "bar",
+            detail,
+            "## Benchmarks (detail)",
+            "",
+            "| Day | Part 1 | Part 1 (median) | Status |",
+            "| :---: | :---: | :---: | :---: |",
+            "| [Day 1](./src/bin/01.rs) | `10ms` | `-` | ❔ ❔ |",
+            "| [Day 2](./src/bin/02.rs) | `30ms` | `-` | ❔ ❔ |",
+            "| [Day 4](./src/bin/04.rs) | `40ms` | `-` | ❔ ❔ |",
+            "",
+            "**Total: 190.00ms**",
+            "",
+            "![Benchmark chart](./.assets/benchmarks.svg)",
+            detail,
+            "baz",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn updates_single_marker() {
+        let mut s = format!("foo\n{MARKER}\nbaz");
+        update_content(&mut s, get_mock_timings(), &Answers::default(), 190.0).unwrap();
+        assert_eq!(s.matches(MARKER).count(), 2);
+        assert!(s.starts_with(&format!("foo\n{MARKER}\n## Benchmarks")));
+        assert!(s.ends_with(&format!("{MARKER}\nbaz")));
+
+        let once = s.clone();
+        update_content(&mut s, get_mock_timings(), &Answers::default(), 190.0).unwrap();
+        assert_eq!(s, once);
+    }
+
+    #[test]
+    fn updates_single_named_marker_next_to_pair() {
+        let summary = "<!--- benchmarking table summary --->";
+        let mut s = format!("{MARKER}{MARKER}\n{summary}");
+        update_content(&mut s, get_mock_timings(), &Answers::default(), 190.0).unwrap();
+        assert_eq!(s.matches(summary).count(), 2);
+        assert_eq!(s.matches("## Benchmarks (summary)").count(), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_for_unknown_columns() {
+        let marker = "<!--- benchmarking table columns=day,memory --->";
+        let mut s = format!("{marker}{marker}");
+        update_content(&mut s, get_mock_timings(), &Answers::default(), 190.0).unwrap();
+    }
+
+    #[test]
+    fn checks_status_against_stored_answers() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_answer = Some("42".into());
+        timings.data[0].part_2_answer = Some("7".into());
+        timings.data[1].part_1_answer = Some("1".into());
+        timings.data[2].part_2 = None;
+        timings.data[2].timed_out = true;
+        let answers = Answers {
+            data: vec![
+                Answer {
+                    day: day!(1),
+                    part_1: Some("42".into()),
+                    part_2: Some("8".into()),
+                },
+                Answer {
+                    day: day!(2),
+                    part_1: None,
+                    part_2: Some("2".into()),
+                },
+            ],
+        };
+
+        let marker = "<!--- benchmarking table columns=day,status chart=off --->";
+        let mut s = format!("{marker}{marker}");
+        update_content(&mut s, timings, &answers, 190.0).unwrap();
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | ⭐ ✘ |"));
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) | ❔ ❔ |"));
+        assert!(s.contains("| [Day 4](./src/bin/04.rs) | ❔ ⌛ |"));
+    }
+
+    #[test]
+    fn formats_parse_times_and_allocations() {
+        let mut timings = get_mock_timings();
+        timings.data[0].parse = Some("2ms".into());
+        timings.data[0].part_1_allocations = Some(1200);
+        timings.data[1].part_2_allocations = Some(0);
+
+        let marker =
+            "<!--- benchmarking table columns=day,parse,allocations_1,allocations_2 chart=off --->";
+        let mut s = format!("{marker}{marker}");
+        update_content(&mut s, timings, &Answers::default(), 190.0).unwrap();
+        let expected = [
+            marker,
+            "## Benchmarks",
+            "",
+            "| Day | Parse | Part 1 (allocations) | Part 2 (allocations) |",
+            "| :---: | :---: | :---: | :---: |",
+            "| [Day 1](./src/bin/01.rs) | `2ms` | `1200` | `-` |",
+            "| [Day 2](./src/bin/02.rs) | `-` | `-` | `0` |",
+            "| [Day 4](./src/bin/04.rs) | `-` | `-` | `-` |",
+            "",
+            "**Total: 190.00ms**",
+            marker,
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn builds_bars() {
+        assert_eq!(bar(0.0, 10.0), "");
+        assert_eq!(bar(10.0, 10.0), "█".repeat(20));
+        assert_eq!(bar(1.0, 10.0), "██");
+        assert_eq!(bar(0.01, 10.0), "▏");
+    }
 }
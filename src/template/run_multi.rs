@@ -4,11 +4,32 @@ use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day};
 
 use super::{
     all_days,
+    answers::Answers,
+    limits::Limits,
+    summary::DaySummary,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+/// Runs the given days one after another. Progress goes to stderr instead of stdout if
+/// `progress_to_stderr` is set, e.g. to keep stdout free for exported timings.
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    limits: &Limits,
+    progress_to_stderr: bool,
+) -> (Option<Timings>, Vec<DaySummary>) {
+    let progress = progress_printer(progress_to_stderr);
+
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut summaries: Vec<DaySummary> = Vec::with_capacity(days_to_run.len());
+
+    let answers = Answers::read_from_file();
+    let profile = if is_release {
+        Profile::Release
+    } else {
+        Profile::Debug
+    };
 
     let mut need_space = false;
 
@@ -17,32 +38,111 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
         .filter(|day| days_to_run.contains(day))
         .for_each(|day| {
             if need_space {
-                println!();
+                progress("");
             }
             need_space = true;
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+            progress(&format!("{ANSI_BOLD}Day {day}{ANSI_RESET}"));
+            progress("------");
+
+            let Some(output) =
+                child_commands::run_solution(day, is_timed, profile, limits, progress_to_stderr)
+                    .unwrap()
+            else {
+                progress("Not solved.");
+                return;
+            };
+
+            if output.timed_out {
+                let timeout = limits.timeout.unwrap_or_default();
+                progress(&format!(
+                    "{ANSI_BOLD}Timed out{ANSI_RESET} after {timeout:?}."
+                ));
+            }
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            let summary = DaySummary::from_output(day, &output, &answers);
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
+            if !output.lines.is_empty() || output.timed_out {
+                let mut val = child_commands::parse_exec_time(&output.lines, day);
+                val.timed_out = output.timed_out;
+                let [part_1, part_2] = &summary.parts;
+                val.part_1_answer.clone_from(&part_1.answer);
+                val.part_2_answer.clone_from(&part_2.answer);
                 timings.push(val);
             }
+
+            summaries.push(summary);
         });
 
     if is_timed {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
-        println!(
+        progress(&format!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
-        );
-        Some(timings)
+        ));
+        (Some(timings), summaries)
     } else {
-        None
+        (None, summaries)
+    }
+}
+
+/// Runs the timed days again with the DHAT heap profiler and records the allocations of each part.
+/// Allocations are counted in a separate build, so that the profiler does not skew the timings.
+pub fn add_allocations(timings: &mut Timings, limits: &Limits, progress_to_stderr: bool) {
+    let progress = progress_printer(progress_to_stderr);
+
+    for timing in &mut timings.data {
+        progress("");
+        progress(&format!(
+            "{ANSI_BOLD}Day {} (allocations){ANSI_RESET}",
+            timing.day
+        ));
+        progress("------");
+
+        let Some(output) = child_commands::run_solution(
+            timing.day,
+            false,
+            Profile::Dhat,
+            limits,
+            progress_to_stderr,
+        )
+        .unwrap() else {
+            continue;
+        };
+
+        let [part_1, part_2] = child_commands::parse_allocations(&output.lines);
+        timing.part_1_allocations = part_1;
+        timing.part_2_allocations = part_2;
+    }
+}
+
+/// Prints progress to stderr if `to_stderr` is set, to stdout otherwise.
+fn progress_printer(to_stderr: bool) -> impl Fn(&str) {
+    move |line: &str| {
+        if to_stderr {
+            eprintln!("{line}");
+        } else {
+            println!("{line}");
+        }
+    }
+}
+
+/// The cargo profile a solution is built with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Profile {
+    Debug,
+    Release,
+    /// Release with debug info and the `dhat-heap` feature, for counting allocations.
+    Dhat,
+}
+
+impl Profile {
+    fn build_args(self) -> &'static [&'static str] {
+        match self {
+            Profile::Debug => &[],
+            Profile::Release => &["--release"],
+            Profile::Dhat => &["--profile", "dhat", "--features", "dhat-heap"],
+        }
     }
 }
 
@@ -67,39 +167,68 @@ pub fn get_path_for_bin(day: Day) -> String {
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
-    use super::{Error, get_path_for_bin};
-    use crate::template::Day;
+    use super::{Error, Profile, get_path_for_bin};
+    use crate::template::{Day, limits::Limits};
     use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
+        collections::HashMap,
+        io::{self, BufRead, BufReader},
+        path::{Path, PathBuf},
+        process::{Child, Command, Stdio},
+        str::FromStr,
         thread,
+        time::{Duration, Instant},
     };
+    use tinyjson::JsonValue;
+
+    /// Output of a single solution run.
+    pub struct SolutionOutput {
+        /// Lines the solution printed to stdout.
+        pub lines: Vec<String>,
+        /// Whether the solution was killed for exceeding its timeout.
+        pub timed_out: bool,
+        /// Whether the solution was built and exited successfully.
+        pub success: bool,
+    }
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Run the solution bin for a given day. Returns `None` for days that have not been scaffolded yet.
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        profile: Profile,
+        limits: &Limits,
+        output_to_stderr: bool,
+    ) -> Result<Option<SolutionOutput>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+            return Ok(None);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        // NOTE: the solution is built up front and run directly instead of through `cargo run`.
+        // this keeps compile times out of the timeout and allows killing the solution process itself.
+        let Some(exe) = build_solution(day, profile)? else {
+            return Ok(Some(SolutionOutput {
+                lines: vec![],
+                timed_out: false,
+                success: false,
+            }));
+        };
 
-        if is_release {
-            args.push("--release");
-        }
+        let mut args = vec![];
 
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
+        if profile == Profile::Dhat {
+            // only count allocations instead of writing a DHAT report for every solution.
+            args.push("--allocations");
+        }
+
         // spawn child command with piped stdout/stderr.
         // forward output to stdout/stderr while grabbing stdout lines.
 
-        let mut cmd = Command::new("cargo")
+        let mut cmd = command_with_limits(&exe, limits)
             .args(&args)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
@@ -108,24 +237,126 @@ pub mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
-
-        let thread = thread::spawn(move || {
+        let stderr_thread = thread::spawn(move || {
             stderr.lines().for_each(|line| {
                 eprintln!("{}", line.unwrap());
             });
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        let stdout_thread = thread::spawn(move || {
+            let mut output = vec![];
+            for line in stdout.lines() {
+                let line = line.unwrap();
+                if output_to_stderr {
+                    eprintln!("{line}");
+                } else {
+                    println!("{line}");
+                }
+                output.push(line);
+            }
+            output
+        });
+
+        let (timed_out, success) = match limits.timeout {
+            Some(timeout) => wait_with_timeout(&mut cmd, timeout)?,
+            None => (false, cmd.wait()?.success()),
+        };
+
+        stderr_thread.join().unwrap();
+        let lines = stdout_thread.join().unwrap();
+
+        Ok(Some(SolutionOutput {
+            lines,
+            timed_out,
+            success,
+        }))
+    }
+
+    /// Build the solution bin for a given day and return the path of its executable.
+    /// Returns `None` if compilation failed.
+    fn build_solution(day: Day, profile: Profile) -> Result<Option<PathBuf>, Error> {
+        let day_padded = day.to_string();
+        let mut args = vec![
+            "build",
+            "--quiet",
+            "--message-format=json-render-diagnostics",
+            "--bin",
+            &day_padded,
+        ];
+        args.extend(profile.build_args());
+
+        // NOTE: the location of the binary depends on e.g. `--target` or `build.target`, so cargo is asked for it.
+        let output = Command::new("cargo")
+            .args(&args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .output()?;
+
+        if !output.status.success() {
+            return Ok(None);
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .find_map(|message| parse_executable(message, day))
+            .map(Some)
+            .ok_or_else(|| io::Error::other("cargo did not report the solution executable.").into())
+    }
+
+    /// Returns the path of the solution binary if `message` is the `compiler-artifact` message for it.
+    fn parse_executable(message: &str, day: Day) -> Option<PathBuf> {
+        let json = JsonValue::from_str(message).ok()?;
+        let json = json.get::<HashMap<String, JsonValue>>()?;
+
+        let name = json
+            .get("target")?
+            .get::<HashMap<String, JsonValue>>()?
+            .get("name")?
+            .get::<String>()?;
+        if *name != day.to_string() {
+            return None;
+        }
+
+        json.get("executable")?.get::<String>().map(PathBuf::from)
+    }
+
+    /// Creates the command for a solution binary, applying the memory limit if set.
+    fn command_with_limits(exe: &Path, limits: &Limits) -> Command {
+        match limits.memory {
+            // NOTE: `ulimit -v` sets `RLIMIT_AS` for the shell, which `exec` then hands to the solution.
+            Some(bytes) if cfg!(unix) => {
+                let mut cmd = Command::new("sh");
+                cmd.arg("-c")
+                    .arg(format!("ulimit -v {} && exec \"$0\" \"$@\"", bytes / 1024))
+                    .arg(exe);
+                cmd
+            }
+            Some(_) => {
+                eprintln!("Memory limits are only supported on unix systems, ignoring.");
+                Command::new(exe)
+            }
+            None => Command::new(exe),
+        }
+    }
+
+    /// Waits for `cmd` to exit, killing it once `timeout` has passed.
+    /// Returns whether it was killed and whether it exited successfully.
+    fn wait_with_timeout(cmd: &mut Child, timeout: Duration) -> Result<(bool, bool), Error> {
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            if let Some(status) = cmd.try_wait()? {
+                return Ok((false, status.success()));
+            }
+
+            if Instant::now() >= deadline {
+                cmd.kill()?;
+                cmd.wait()?;
+                return Ok((true, false));
+            }
 
-        Ok(output)
+            thread::sleep(Duration::from_millis(10));
+        }
     }
 
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
@@ -133,13 +364,27 @@ pub mod child_commands {
             day,
             part_1: None,
             part_2: None,
+            part_1_median: None,
+            part_2_median: None,
             total_nanos: 0_f64,
+            timed_out: false,
+            parse: None,
+            part_1_allocations: None,
+            part_2_allocations: None,
+            part_1_answer: None,
+            part_2_answer: None,
         };
 
         output
             .iter()
             .filter_map(|l| {
-                if !l.contains(" samples)") {
+                if !l.contains(" samples") {
+                    return None;
+                }
+
+                // NOTE: solutions with shared state also print a combined timing that is not part of the total.
+                let part = l.split(':').next()?;
+                if !part.contains("Part ") && !part.ends_with("Parse") {
                     return None;
                 }
 
@@ -148,14 +393,17 @@ pub mod child_commands {
                     return None;
                 };
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
+                Some((part, timing_str, parse_median(l), nanos))
             })
-            .for_each(|(part, timing_str, nanos)| {
+            .for_each(|(part, timing_str, median, nanos)| {
                 if part.contains("Part 1") {
                     timings.part_1 = Some(timing_str.into());
+                    timings.part_1_median = median.map(Into::into);
                 } else if part.contains("Part 2") {
                     timings.part_2 = Some(timing_str.into());
+                    timings.part_2_median = median.map(Into::into);
+                } else {
+                    timings.parse = Some(timing_str.into());
                 }
 
                 timings.total_nanos += nanos;
@@ -164,14 +412,23 @@ pub mod child_commands {
         timings
     }
 
+    /// Parses the `Part N allocations: X` lines printed by solutions built with `dhat-heap`.
+    pub fn parse_allocations(output: &[String]) -> [Option<u64>; 2] {
+        [1, 2].map(|part| {
+            let prefix = format!("Part {part} allocations: ");
+            output
+                .iter()
+                .find_map(|l| l.strip_prefix(&prefix)?.trim().parse().ok())
+        })
+    }
+
     fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
         s.split(postfix).next()?.parse().ok()
     }
 
     fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
         let str_timing = line
-            .split(" samples)")
+            .split(" samples")
             .next()?
             .split('(')
             .next_back()?
@@ -179,14 +436,25 @@ pub mod child_commands {
             .next()?
             .trim();
 
-        let parsed_timing = match str_timing {
+        Some((str_timing, parse_duration_nanos(str_timing)?))
+    }
+
+    fn parse_median(line: &str) -> Option<&str> {
+        line.rsplit_once(", median ")?
+            .1
+            .trim_end()
+            .strip_suffix(')')
+    }
+
+    /// Converts a formatted [`std::time::Duration`] such as `74.13µs` back to nanoseconds.
+    pub fn parse_duration_nanos(s: &str) -> Option<f64> {
+        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+        match s {
             s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
             s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
             s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
             s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+        }
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -205,7 +473,8 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_allocations, parse_exec_time, parse_executable};
+        use std::path::PathBuf;
 
         use crate::day;
 
@@ -239,6 +508,86 @@ pub mod child_commands {
             assert_eq!(res.part_2.unwrap(), "100ms");
         }
 
+        #[test]
+        fn parses_median_times() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 1 (10.2ms @ 100 samples, median 9.8ms)".into(),
+                    "Part 2: median (5µs @ 200 samples, median 4.5µs)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 10205000_f64);
+            assert_eq!(res.part_1.unwrap(), "10.2ms");
+            assert_eq!(res.part_1_median.unwrap(), "9.8ms");
+            assert_eq!(res.part_2.unwrap(), "5µs");
+            assert_eq!(res.part_2_median.unwrap(), "4.5µs");
+        }
+
+        #[test]
+        fn ignores_combined_timings() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 1 (10ms @ 100 samples)".into(),
+                    "Part 2: 2 (5ms @ 200 samples)".into(),
+                    " > benching\rCombined: (15ms @ 60 samples)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 15000000_f64);
+            assert_eq!(res.part_1.unwrap(), "10ms");
+            assert_eq!(res.part_2.unwrap(), "5ms");
+        }
+
+        #[test]
+        fn parses_parse_times() {
+            let res = parse_exec_time(
+                &[
+                    " > benching\rParse: (2ms @ 500 samples, median 1.9ms)".into(),
+                    "Part 1: 1 (10ms @ 100 samples)".into(),
+                    "Part 2: 2 (5ms @ 200 samples)".into(),
+                    " > benching\rCombined: (17ms @ 60 samples)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 17000000_f64);
+            assert_eq!(res.parse.unwrap(), "2ms");
+            assert_eq!(res.part_1.unwrap(), "10ms");
+        }
+
+        #[test]
+        fn parses_allocations() {
+            let res = parse_allocations(&[
+                "Part 1: 1 (10ms)".into(),
+                "Part 1 allocations: 12".into(),
+                "Part 2: ✖        ".into(),
+                "".into(),
+            ]);
+            assert_eq!(res, [Some(12), None]);
+        }
+
+        #[test]
+        fn parses_executable_from_build_messages() {
+            let artifact = r#"{"reason":"compiler-artifact","target":{"kind":["bin"],"name":"01"},"filenames":["/repo/target/x86_64-unknown-linux-gnu/release/01"],"executable":"/repo/target/x86_64-unknown-linux-gnu/release/01","fresh":true}"#;
+            assert_eq!(
+                parse_executable(artifact, day!(1)),
+                Some(PathBuf::from(
+                    "/repo/target/x86_64-unknown-linux-gnu/release/01"
+                ))
+            );
+            assert_eq!(parse_executable(artifact, day!(2)), None);
+
+            let library = r#"{"reason":"compiler-artifact","target":{"kind":["lib"],"name":"01"},"executable":null}"#;
+            assert_eq!(parse_executable(library, day!(1)), None);
+            assert_eq!(
+                parse_executable(r#"{"reason":"build-finished","success":true}"#, day!(1)),
+                None
+            );
+        }
+
         #[test]
         fn parses_missing_parts() {
             let res = parse_exec_time(
@@ -12,31 +12,106 @@ use crate::template::{ANSI_ITALIC, ANSI_RESET, Day, aoc_cli};
 pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
+    let (result, measurement) =
         run_timed(func, input, |result| print_result(result, &part_str, ""));
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    print_result(&result, &part_str, &format_duration(&measurement));
+    print_allocations(&part_str, &measurement);
 
     if let Some(result) = result {
         submit_result(result, day, part);
     }
 }
 
+/// Run both parts of a solution that share the parsed input.
+///
+/// Parsing and each part are timed on their own, both parts being handed the result of the first parse.
+/// The combined time of parsing and computing both answers from scratch is printed afterwards.
+pub fn run_shared<S, T: Display, U: Display>(
+    parse: impl Fn(&str) -> Option<S>,
+    part_one: impl Fn(&S) -> Option<T>,
+    part_two: impl Fn(&S) -> Option<U>,
+    input: &str,
+    day: Day,
+) {
+    let (state, measurement_parse) = run_timed(&parse, input, |_| {});
+    print!("\r");
+    println!("Parse:{}", format_duration(&measurement_parse));
+
+    // NOTE: both parts fail if the input cannot be parsed.
+    let state = state.as_ref();
+
+    let (result_one, measurement_one) = run_timed(
+        |state: Option<&S>| state.and_then(&part_one),
+        state,
+        |result| print_result(result, "Part 1", ""),
+    );
+
+    print_result(&result_one, "Part 1", &format_duration(&measurement_one));
+    print_allocations("Part 1", &measurement_one);
+
+    let (result_two, measurement_two) = run_timed(
+        |state: Option<&S>| state.and_then(&part_two),
+        state,
+        |result| print_result(result, "Part 2", ""),
+    );
+
+    print_result(&result_two, "Part 2", &format_duration(&measurement_two));
+    print_allocations("Part 2", &measurement_two);
+
+    let base_time = measurement_parse.average + measurement_one.average + measurement_two.average;
+    let combined = if std::env::args().any(|x| x == "--time") {
+        let combined = bench(
+            |input| {
+                let state = parse(input)?;
+                Some((part_one(&state), part_two(&state)))
+            },
+            input,
+            &base_time,
+        );
+        print!("\r");
+        combined
+    } else {
+        Measurement::single(base_time)
+    };
+
+    println!("Combined:{}", format_duration(&combined));
+
+    if let Some(result) = result_one {
+        submit_result(result, day, 1);
+    }
+
+    if let Some(result) = result_two {
+        submit_result(result, day, 2);
+    }
+}
+
+/// Execution time statistics of a solution part.
+struct Measurement {
+    average: Duration,
+    median: Duration,
+    samples: u128,
+    /// Heap allocations of the first run, only counted with the `dhat-heap` feature.
+    allocations: Option<u64>,
+}
+
+impl Measurement {
+    fn single(duration: Duration) -> Self {
+        Self {
+            average: duration,
+            median: duration,
+            samples: 1,
+            allocations: None,
+        }
+    }
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Copy, T>(
-    func: impl Fn(I) -> T,
-    input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+fn run_timed<I: Copy, T>(func: impl Fn(I) -> T, input: I, hook: impl Fn(&T)) -> (T, Measurement) {
     let timer = Instant::now();
-    let result = {
-        #[cfg(feature = "dhat-heap")]
-        let _profiler = dhat::Profiler::new_heap();
-
-        func(input)
-    };
+    let (result, allocations) = count_allocations(|| func(input));
     let base_time = timer.elapsed();
 
     hook(&result);
@@ -44,13 +119,32 @@ fn run_timed<I: Copy, T>(
     let run = if std::env::args().any(|x| x == "--time") {
         bench(func, input, &base_time)
     } else {
-        (base_time, 1)
+        Measurement::single(base_time)
+    };
+
+    (result, Measurement { allocations, ..run })
+}
+
+/// Runs `func` under the DHAT heap profiler and returns its result with the number of heap allocations it made.
+#[cfg(feature = "dhat-heap")]
+fn count_allocations<T>(func: impl FnOnce() -> T) -> (T, Option<u64>) {
+    // NOTE: `--allocations` is passed by `cargo time --allocations`, which only needs the count and not the report.
+    let _profiler = if env::args().any(|x| x == "--allocations") {
+        dhat::Profiler::builder().testing().build()
+    } else {
+        dhat::Profiler::new_heap()
     };
 
-    (result, run.0, run.1)
+    let result = func();
+    (result, Some(dhat::HeapStats::get().total_blocks))
+}
+
+#[cfg(not(feature = "dhat-heap"))]
+fn count_allocations<T>(func: impl FnOnce() -> T) -> (T, Option<u64>) {
+    (func(), None)
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> Measurement {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
@@ -67,11 +161,13 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (
         timers.push(timer.elapsed());
     }
 
-    (
+    Measurement {
         #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+        average: Duration::from_nanos(average_duration(&timers) as u64),
+        median: median_duration(&mut timers),
+        samples: bench_iterations,
+        allocations: None,
+    }
 }
 
 fn average_duration(numbers: &[Duration]) -> u128 {
@@ -82,11 +178,23 @@ fn average_duration(numbers: &[Duration]) -> u128 {
         / numbers.len() as u128
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
+fn median_duration(numbers: &mut [Duration]) -> Duration {
+    numbers.sort_unstable();
+    numbers[numbers.len() / 2]
+}
+
+fn format_duration(measurement: &Measurement) -> String {
+    let Measurement {
+        average,
+        median,
+        samples,
+        ..
+    } = measurement;
+
+    if *samples == 1 {
+        format!(" ({average:.1?})")
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(" ({average:.1?} @ {samples} samples, median {median:.1?})")
     }
 }
 
@@ -125,6 +233,28 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
     }
 }
 
+fn print_allocations(part: &str, measurement: &Measurement) {
+    if let Some(allocations) = measurement.allocations {
+        println!("{part} allocations: {allocations}");
+    }
+}
+
+/// Parse the `--generate <seed> <size>` arguments passed by the `generate` command.
+/// Returns [`None`] if the solution should be run instead.
+pub fn generate_args() -> Option<(u64, usize)> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|x| x == "--generate")?;
+
+    let seed = args.get(index + 1).and_then(|x| x.parse().ok());
+    let size = args.get(index + 2).and_then(|x| x.parse().ok());
+    let (Some(seed), Some(size)) = (seed, size) else {
+        eprintln!("Unexpected command-line input. Format: cargo generate 1 --seed 42 --size 1000");
+        process::exit(1);
+    };
+
+    Some((seed, size))
+}
+
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
 ///  2. aoc-cli is installed.
@@ -0,0 +1,319 @@
+/// Module that condenses the output of solution runs into a per-day summary table.
+use crate::template::answers::{Answer, Answers};
+use crate::template::run_multi::child_commands::SolutionOutput;
+use crate::template::{ANSI_BOLD, ANSI_RESET, Day};
+
+/// Outcome of a single part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PartStatus {
+    /// The part produced an answer, but there is no stored answer to compare against.
+    Solved,
+    /// The part produced the stored answer.
+    Correct,
+    /// The part produced an answer different from the stored one.
+    Wrong { expected: String },
+    /// The part returned `None`.
+    Unsolved,
+    /// The solution failed to build or crashed before finishing the part.
+    Error,
+    /// The solution was killed for exceeding its timeout before finishing the part.
+    Timeout,
+}
+
+#[derive(Debug, Clone)]
+pub struct PartSummary {
+    pub status: PartStatus,
+    pub answer: Option<String>,
+    pub time: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DaySummary {
+    pub day: Day,
+    pub parts: [PartSummary; 2],
+}
+
+impl PartStatus {
+    pub fn is_failure(&self) -> bool {
+        matches!(
+            self,
+            PartStatus::Wrong { .. } | PartStatus::Error | PartStatus::Timeout
+        )
+    }
+}
+
+impl DaySummary {
+    pub fn from_output(day: Day, output: &SolutionOutput, answers: &Answers) -> Self {
+        let parts = [1, 2].map(|part| {
+            let Some((answer, time)) = find_part(&output.lines, part) else {
+                let status = if output.timed_out {
+                    PartStatus::Timeout
+                } else if output.success {
+                    PartStatus::Unsolved
+                } else {
+                    PartStatus::Error
+                };
+                return PartSummary {
+                    status,
+                    answer: None,
+                    time: None,
+                };
+            };
+
+            let status = match (&answer, answers.get(day, part)) {
+                (None, _) => PartStatus::Unsolved,
+                (Some(_), None) => PartStatus::Solved,
+                (Some(answer), Some(expected)) if answer == expected => PartStatus::Correct,
+                (Some(_), Some(expected)) => PartStatus::Wrong {
+                    expected: expected.into(),
+                },
+            };
+
+            PartSummary {
+                status,
+                answer,
+                time,
+            }
+        });
+
+        DaySummary { day, parts }
+    }
+
+    pub fn has_failures(&self) -> bool {
+        self.parts.iter().any(|p| p.status.is_failure())
+    }
+
+    /// The answers produced in this run, for storing them as known answers.
+    pub fn answers(&self) -> Answer {
+        let [part_1, part_2] = &self.parts;
+        Answer {
+            day: self.day,
+            part_1: part_1.answer.clone(),
+            part_2: part_2.answer.clone(),
+        }
+    }
+}
+
+/// Finds the final result line of a part and returns its answer (`None` for ✖) and time.
+/// Returns `None` if the part did not finish.
+#[allow(clippy::option_option)]
+fn find_part(lines: &[String], part: u8) -> Option<(Option<String>, Option<String>)> {
+    let prefix = format!("Part {part}: ");
+
+    // NOTE: final results are printed after a carriage return that overwrites the intermediate result.
+    let index = lines.iter().rposition(|line| {
+        line.rsplit_once('\r')
+            .is_some_and(|(_, last)| last.starts_with(&prefix))
+    })?;
+
+    let (_, line) = lines[index].rsplit_once('\r')?;
+    let rest = line.strip_prefix(&prefix)?;
+
+    if rest.starts_with('✖') {
+        return Some((None, None));
+    }
+
+    if let Some(duration) = rest.strip_prefix("▼ ") {
+        // NOTE: multi-line answers are printed on the lines following the result line.
+        let answer = lines[index + 1..]
+            .iter()
+            .take_while(|l| !l.starts_with("Part ") && !l.contains('\r') && !l.is_empty())
+            .cloned()
+            .collect::<Vec<_>>()
+            .join("\n");
+        return Some((Some(answer), parse_time(duration)));
+    }
+
+    let (answer, duration) = rest.strip_prefix(ANSI_BOLD)?.split_once(ANSI_RESET)?;
+    Some((Some(answer.into()), parse_time(duration)))
+}
+
+fn parse_time(s: &str) -> Option<String> {
+    let inner = s.trim().strip_prefix('(')?.strip_suffix(')')?;
+    Some(inner.split(" @").next()?.trim().into())
+}
+
+fn format_part(part: &PartSummary) -> String {
+    let answer = match part.answer.as_deref() {
+        Some(answer) if answer.contains('\n') => "▼",
+        Some(answer) => answer,
+        None => "",
+    };
+    let time = part
+        .time
+        .as_ref()
+        .map(|t| format!(" ({t})"))
+        .unwrap_or_default();
+
+    match &part.status {
+        PartStatus::Solved => format!("{answer}{time}"),
+        PartStatus::Correct => format!("✔ {answer}{time}"),
+        PartStatus::Wrong { expected } => format!("✘ {answer}, expected {expected}{time}"),
+        PartStatus::Unsolved => "✖".into(),
+        PartStatus::Error => "error".into(),
+        PartStatus::Timeout => "timeout".into(),
+    }
+}
+
+/// Renders the summary as a plain text table.
+pub fn render(summaries: &[DaySummary]) -> String {
+    let rows: Vec<[String; 3]> = summaries
+        .iter()
+        .map(|s| {
+            [
+                s.day.to_string(),
+                format_part(&s.parts[0]),
+                format_part(&s.parts[1]),
+            ]
+        })
+        .collect();
+
+    let header = ["Day".to_string(), "Part 1".into(), "Part 2".into()];
+
+    let mut widths = [0; 3];
+    for row in std::iter::once(&header).chain(&rows) {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let format_row = |row: &[String; 3]| {
+        row.iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{cell}{}", " ".repeat(width - cell.chars().count())))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    let mut lines = vec![format_row(&header)];
+    lines.extend(rows.iter().map(format_row));
+    lines.join("\n")
+}
+
+pub fn print_summary(summaries: &[DaySummary]) {
+    let failures = summaries.iter().filter(|s| s.has_failures()).count();
+
+    println!("\n{ANSI_BOLD}Summary{ANSI_RESET}");
+    println!("-------");
+    println!("{}", render(summaries));
+
+    if failures > 0 {
+        println!("\n{ANSI_BOLD}{failures} day(s) failed.{ANSI_RESET}");
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{DaySummary, PartStatus, render};
+    use crate::day;
+    use crate::template::answers::{Answer, Answers};
+    use crate::template::run_multi::child_commands::SolutionOutput;
+
+    fn output(lines: &[&str], timed_out: bool, success: bool) -> SolutionOutput {
+        SolutionOutput {
+            lines: lines.iter().map(|l| l.to_string()).collect(),
+            timed_out,
+            success,
+        }
+    }
+
+    fn get_mock_answers() -> Answers {
+        Answers {
+            data: vec![Answer {
+                day: day!(1),
+                part_1: Some("42".into()),
+                part_2: Some("7".into()),
+            }],
+        }
+    }
+
+    #[test]
+    fn compares_with_stored_answers() {
+        let output = output(
+            &[
+                "Part 1: \x1b[1m42\x1b[0m\rPart 1: \x1b[1m42\x1b[0m (1.2ms)",
+                "Part 2: \x1b[1m8\x1b[0m > benching\rPart 2: \x1b[1m8\x1b[0m (3.0µs @ 100 samples, median 2.9µs)",
+            ],
+            false,
+            true,
+        );
+        let summary = DaySummary::from_output(day!(1), &output, &get_mock_answers());
+        assert_eq!(summary.parts[0].status, PartStatus::Correct);
+        assert_eq!(summary.parts[0].time.as_deref(), Some("1.2ms"));
+        assert_eq!(
+            summary.parts[1].status,
+            PartStatus::Wrong {
+                expected: "7".into()
+            }
+        );
+        assert_eq!(summary.parts[1].answer.as_deref(), Some("8"));
+        assert_eq!(summary.parts[1].time.as_deref(), Some("3.0µs"));
+        assert!(summary.has_failures());
+    }
+
+    #[test]
+    fn handles_unsolved_and_unknown_parts() {
+        let output = output(
+            &[
+                "Part 1: ✖\rPart 1: ✖             ",
+                "Part 2: \x1b[1m8\x1b[0m\rPart 2: \x1b[1m8\x1b[0m (1ns)",
+            ],
+            false,
+            true,
+        );
+        let summary = DaySummary::from_output(day!(2), &output, &get_mock_answers());
+        assert_eq!(summary.parts[0].status, PartStatus::Unsolved);
+        assert_eq!(summary.parts[1].status, PartStatus::Solved);
+        assert!(!summary.has_failures());
+    }
+
+    #[test]
+    fn handles_timeouts_and_errors() {
+        let timed_out = output(
+            &["Part 1: \x1b[1m42\x1b[0m\rPart 1: \x1b[1m42\x1b[0m (1.2ms)"],
+            true,
+            false,
+        );
+        let summary = DaySummary::from_output(day!(1), &timed_out, &get_mock_answers());
+        assert_eq!(summary.parts[0].status, PartStatus::Correct);
+        assert_eq!(summary.parts[1].status, PartStatus::Timeout);
+
+        let crashed = output(&["Part 1: \x1b[1m42\x1b[0m"], false, false);
+        let summary = DaySummary::from_output(day!(1), &crashed, &get_mock_answers());
+        assert_eq!(summary.parts[0].status, PartStatus::Error);
+        assert_eq!(summary.parts[1].status, PartStatus::Error);
+    }
+
+    #[test]
+    fn handles_multi_line_answers() {
+        let output = output(
+            &[
+                "Part 1: ▼ \rPart 1: ▼  (5.0µs)",
+                "#..#",
+                ".##.",
+                "Part 2: ✖\rPart 2: ✖             ",
+            ],
+            false,
+            true,
+        );
+        let summary = DaySummary::from_output(day!(5), &output, &Answers::default());
+        assert_eq!(summary.parts[0].answer.as_deref(), Some("#..#\n.##."));
+        assert_eq!(summary.parts[0].time.as_deref(), Some("5.0µs"));
+    }
+
+    #[test]
+    fn renders_table() {
+        let output = output(
+            &["Part 1: \x1b[1m42\x1b[0m\rPart 1: \x1b[1m42\x1b[0m (1.2ms)"],
+            false,
+            true,
+        );
+        let summary = DaySummary::from_output(day!(1), &output, &get_mock_answers());
+        let expected = ["Day  Part 1        Part 2", "01   ✔ 42 (1.2ms)  ✖"].join("\n");
+        assert_eq!(render(&[summary]), expected);
+    }
+}
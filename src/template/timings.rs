@@ -11,7 +11,19 @@ pub struct Timing {
     pub day: Day,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    pub part_1_median: Option<String>,
+    pub part_2_median: Option<String>,
     pub total_nanos: f64,
+    /// Whether the run was killed for exceeding its timeout. Parts without a timing did not finish.
+    pub timed_out: bool,
+    /// Time spent parsing the input, for days that parse once for both parts.
+    pub parse: Option<String>,
+    /// Heap allocations of each part, counted by `cargo time --allocations`.
+    pub part_1_allocations: Option<u64>,
+    pub part_2_allocations: Option<u64>,
+    /// Answers of the timed run, compared against the stored answers in the benchmark table.
+    pub part_1_answer: Option<String>,
+    pub part_2_answer: Option<String>,
 }
 
 /// Represents benchmark times for a set of days.
@@ -51,7 +63,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -113,6 +125,7 @@ impl From<&Timing> for JsonValue {
 
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
+        map.insert("timed_out".into(), JsonValue::Boolean(value.timed_out));
 
         let part_1 = value.part_1.clone().map(JsonValue::String);
         let part_2 = value.part_2.clone().map(JsonValue::String);
@@ -133,6 +146,30 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        for (key, optional) in [
+            ("part_1_median", &value.part_1_median),
+            ("part_2_median", &value.part_2_median),
+            ("parse", &value.parse),
+            ("part_1_answer", &value.part_1_answer),
+            ("part_2_answer", &value.part_2_answer),
+        ] {
+            map.insert(
+                key.into(),
+                optional.clone().map_or(JsonValue::Null, JsonValue::String),
+            );
+        }
+
+        for (key, allocations) in [
+            ("part_1_allocations", value.part_1_allocations),
+            ("part_2_allocations", value.part_2_allocations),
+        ] {
+            #[allow(clippy::cast_precision_loss)]
+            map.insert(
+                key.into(),
+                allocations.map_or(JsonValue::Null, |x| JsonValue::Number(x as f64)),
+            );
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -161,6 +198,24 @@ impl TryFrom<&JsonValue> for Timing {
             .map(|v| if v.is_null() { None } else { v.get::<String>() })
             .ok_or("Expected timing.part_2 to be null or string.")?;
 
+        // NOTE: medians and timeouts were added later, timings stored before that do not contain them.
+        let part_1_median = json.get("part_1_median").and_then(|v| v.get::<String>());
+        let part_2_median = json.get("part_2_median").and_then(|v| v.get::<String>());
+
+        // NOTE: parse times, allocations and answers were added later as well.
+        let optional = |key: &str| json.get(key).and_then(|v| v.get::<String>()).cloned();
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let allocations = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|&x| x as u64)
+        };
+
+        let timed_out = json
+            .get("timed_out")
+            .and_then(|v| v.get::<bool>().copied())
+            .unwrap_or(false);
+
         let total_nanos = json
             .get("total_nanos")
             .and_then(|v| v.get::<f64>().copied())
@@ -170,7 +225,15 @@ impl TryFrom<&JsonValue> for Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
+            part_1_median: part_1_median.cloned(),
+            part_2_median: part_2_median.cloned(),
             total_nanos,
+            timed_out,
+            parse: optional("parse"),
+            part_1_allocations: allocations("part_1_allocations"),
+            part_2_allocations: allocations("part_2_allocations"),
+            part_1_answer: optional("part_1_answer"),
+            part_2_answer: optional("part_2_answer"),
         })
     }
 }
@@ -190,19 +253,43 @@ mod tests {
                     day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    part_1_median: None,
+                    part_2_median: None,
                     total_nanos: 3e+10,
+                    timed_out: false,
+                    parse: None,
+                    part_1_allocations: None,
+                    part_2_allocations: None,
+                    part_1_answer: None,
+                    part_2_answer: None,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    part_1_median: None,
+                    part_2_median: None,
                     total_nanos: 7e+10,
+                    timed_out: false,
+                    parse: None,
+                    part_1_allocations: None,
+                    part_2_allocations: None,
+                    part_1_answer: None,
+                    part_2_answer: None,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: None,
+                    part_1_median: None,
+                    part_2_median: None,
                     total_nanos: 4e+10,
+                    timed_out: false,
+                    parse: None,
+                    part_1_allocations: None,
+                    part_2_allocations: None,
+                    part_1_answer: None,
+                    part_2_answer: None,
                 },
             ],
         }
@@ -221,6 +308,29 @@ mod tests {
             assert_eq!(timing.part_1, Some("1ms".to_string()));
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(timing.timed_out, false);
+        }
+
+        #[test]
+        fn handles_timed_out_timings() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "part_1_median": "0.9ms", "part_2_median": null, "total_nanos": 1000000, "timed_out": true }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_1_median, Some("0.9ms".to_string()));
+            assert_eq!(timing.part_2_median, None);
+            assert_eq!(timing.timed_out, true);
+        }
+
+        #[test]
+        fn handles_parse_times_allocations_and_answers() {
+            let json = r#"{ "data": [{ "day": "08", "part_1": "1ms", "part_2": "2ms", "parse": "3ms", "part_1_allocations": 12, "part_2_allocations": null, "part_1_answer": "40", "total_nanos": 6000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.parse, Some("3ms".to_string()));
+            assert_eq!(timing.part_1_allocations, Some(12));
+            assert_eq!(timing.part_2_allocations, None);
+            assert_eq!(timing.part_1_answer, Some("40".to_string()));
+            assert_eq!(timing.part_2_answer, None);
         }
 
         #[test]
@@ -281,11 +391,19 @@ mod tests {
                     day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
+                    part_1_median: None,
+                    part_2_median: None,
                     total_nanos: 3_000_000_000_f64,
+                    timed_out: false,
+                    parse: None,
+                    part_1_allocations: None,
+                    part_2_allocations: None,
+                    part_1_answer: None,
+                    part_2_answer: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -295,11 +413,19 @@ mod tests {
                     day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: None,
+                    part_1_median: None,
+                    part_2_median: None,
                     total_nanos: 1_000_000_000_f64,
+                    timed_out: false,
+                    parse: None,
+                    part_1_allocations: None,
+                    part_2_allocations: None,
+                    part_1_answer: None,
+                    part_2_answer: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -309,11 +435,19 @@ mod tests {
                     day: day!(1),
                     part_1: None,
                     part_2: None,
+                    part_1_median: None,
+                    part_2_median: None,
                     total_nanos: 0.0,
+                    timed_out: false,
+                    parse: None,
+                    part_1_allocations: None,
+                    part_2_allocations: None,
+                    part_1_answer: None,
+                    part_2_answer: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
@@ -333,7 +467,15 @@ mod tests {
                     day: day!(3),
                     part_1: None,
                     part_2: None,
+                    part_1_median: None,
+                    part_2_median: None,
                     total_nanos: 0_f64,
+                    timed_out: false,
+                    parse: None,
+                    part_1_allocations: None,
+                    part_2_allocations: None,
+                    part_1_answer: None,
+                    part_2_answer: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -353,7 +495,15 @@ mod tests {
                     day: day!(2),
                     part_1: None,
                     part_2: None,
+                    part_1_median: None,
+                    part_2_median: None,
                     total_nanos: 0_f64,
+                    timed_out: false,
+                    parse: None,
+                    part_1_allocations: None,
+                    part_2_allocations: None,
+                    part_1_answer: None,
+                    part_2_answer: None,
                 }],
             };
             let merged = timings.merge(&other);
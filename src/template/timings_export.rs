@@ -0,0 +1,257 @@
+/// Module that exports timing information in formats suitable for external tools.
+/// Unlike `data/timings.json`, the exported shape is flat: one record per day and part.
+use std::{collections::HashMap, error::Error, fmt::Display, fs, io, str::FromStr};
+
+use tinyjson::JsonValue;
+
+use crate::template::Day;
+use crate::template::run_multi::child_commands::parse_duration_nanos;
+use crate::template::timings::Timings;
+
+/// Formats that `cargo time --output-format` can export to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Csv,
+    Json,
+    Markdown,
+}
+
+impl FromStr for OutputFormat {
+    type Err = OutputFormatFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(Self::Csv),
+            "json" => Ok(Self::Json),
+            "markdown" | "md" => Ok(Self::Markdown),
+            _ => Err(OutputFormatFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing an [`OutputFormat`].
+#[derive(Debug)]
+pub struct OutputFormatFromStrError;
+
+impl Error for OutputFormatFromStrError {}
+
+impl Display for OutputFormatFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting one of `csv`, `json` or `markdown`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Benchmark result for a single part of a day.
+struct PartRecord<'a> {
+    day: Day,
+    part: u8,
+    time: Option<&'a str>,
+    nanos: Option<f64>,
+}
+
+fn records(timings: &Timings) -> Vec<PartRecord<'_>> {
+    timings
+        .data
+        .iter()
+        .flat_map(|timing| {
+            [(1, &timing.part_1), (2, &timing.part_2)]
+                .into_iter()
+                .map(|(part, time)| PartRecord {
+                    day: timing.day,
+                    part,
+                    time: time.as_deref(),
+                    nanos: time.as_deref().and_then(parse_duration_nanos),
+                })
+        })
+        .collect()
+}
+
+fn to_csv(timings: &Timings) -> String {
+    let mut lines = vec!["day,part,time,nanos".to_string()];
+
+    for record in records(timings) {
+        lines.push(format!(
+            "{},{},{},{}",
+            record.day.into_inner(),
+            record.part,
+            record.time.unwrap_or_default(),
+            record.nanos.map(|n| n.to_string()).unwrap_or_default()
+        ));
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+fn to_json(timings: &Timings) -> Result<String, io::Error> {
+    let parts = records(timings)
+        .into_iter()
+        .map(|record| {
+            let mut map: HashMap<String, JsonValue> = HashMap::new();
+            map.insert(
+                "day".into(),
+                JsonValue::Number(f64::from(record.day.into_inner())),
+            );
+            map.insert("part".into(), JsonValue::Number(f64::from(record.part)));
+            map.insert(
+                "time".into(),
+                record
+                    .time
+                    .map_or(JsonValue::Null, |t| JsonValue::String(t.into())),
+            );
+            map.insert(
+                "nanos".into(),
+                record.nanos.map_or(JsonValue::Null, JsonValue::Number),
+            );
+            JsonValue::Object(map)
+        })
+        .collect();
+
+    let mut map: HashMap<String, JsonValue> = HashMap::new();
+    map.insert("parts".into(), JsonValue::Array(parts));
+    map.insert(
+        "total_nanos".into(),
+        JsonValue::Number(timings.data.iter().map(|t| t.total_nanos).sum()),
+    );
+
+    JsonValue::Object(map)
+        .format()
+        .map_err(|e| io::Error::other(e.to_string()))
+}
+
+fn to_markdown(timings: &Timings) -> String {
+    let mut lines: Vec<String> = vec![
+        "| Day | Part 1 | Part 2 | Total |".into(),
+        "| :---: | :---: | :---: | :---: |".into(),
+    ];
+
+    for timing in &timings.data {
+        lines.push(format!(
+            "| {} | {} | {} | {:.2}ms |",
+            timing.day.into_inner(),
+            timing.part_1.as_deref().unwrap_or("-"),
+            timing.part_2.as_deref().unwrap_or("-"),
+            timing.total_nanos / 1_000_000_f64
+        ));
+    }
+
+    lines.push(String::new());
+    lines.push(format!("**Total: {:.2}ms**", timings.total_millis()));
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+/// Renders `timings` in the given format.
+pub fn render(timings: &Timings, format: OutputFormat) -> Result<String, io::Error> {
+    match format {
+        OutputFormat::Csv => Ok(to_csv(timings)),
+        OutputFormat::Json => to_json(timings),
+        OutputFormat::Markdown => Ok(to_markdown(timings)),
+    }
+}
+
+/// Renders `timings` in the given format and writes them to `out`, or to stdout if no path is given.
+pub fn export(timings: &Timings, format: OutputFormat, out: Option<&str>) -> Result<(), io::Error> {
+    let rendered = render(timings, format)?;
+    match out {
+        Some(path) => fs::write(path, rendered),
+        None => {
+            print!("{rendered}");
+            Ok(())
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{OutputFormat, render};
+    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use std::{collections::HashMap, str::FromStr};
+    use tinyjson::JsonValue;
+
+    fn get_mock_timings() -> Timings {
+        Timings {
+            data: vec![
+                Timing {
+                    day: day!(1),
+                    part_1: Some("10ms".into()),
+                    part_2: Some("20µs".into()),
+                    part_1_median: None,
+                    part_2_median: None,
+                    total_nanos: 10_020_000_f64,
+                    timed_out: false,
+                    parse: None,
+                    part_1_allocations: None,
+                    part_2_allocations: None,
+                    part_1_answer: None,
+                    part_2_answer: None,
+                },
+                Timing {
+                    day: day!(3),
+                    part_1: Some("1.5s".into()),
+                    part_2: None,
+                    part_1_median: None,
+                    part_2_median: None,
+                    total_nanos: 1_500_000_000_f64,
+                    timed_out: false,
+                    parse: None,
+                    part_1_allocations: None,
+                    part_2_allocations: None,
+                    part_1_answer: None,
+                    part_2_answer: None,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn parses_output_formats() {
+        assert_eq!(OutputFormat::from_str("csv").unwrap(), OutputFormat::Csv);
+        assert_eq!(OutputFormat::from_str("json").unwrap(), OutputFormat::Json);
+        assert_eq!(
+            OutputFormat::from_str("markdown").unwrap(),
+            OutputFormat::Markdown
+        );
+        assert!(OutputFormat::from_str("yaml").is_err());
+    }
+
+    #[test]
+    fn renders_csv() {
+        let csv = render(&get_mock_timings(), OutputFormat::Csv).unwrap();
+        let expected = [
+            "day,part,time,nanos",
+            "1,1,10ms,10000000",
+            "1,2,20µs,20000",
+            "3,1,1.5s,1500000000",
+            "3,2,,",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(csv, expected);
+    }
+
+    #[test]
+    fn renders_json() {
+        let json = render(&get_mock_timings(), OutputFormat::Json).unwrap();
+        let value = JsonValue::from_str(&json).unwrap();
+        let map = value.get::<HashMap<String, JsonValue>>().unwrap();
+        let parts = map.get("parts").unwrap().get::<Vec<JsonValue>>().unwrap();
+        assert_eq!(parts.len(), 4);
+        let last = parts[3].get::<HashMap<String, JsonValue>>().unwrap();
+        assert_eq!(last.get("day").unwrap().get::<f64>(), Some(&3.0));
+        assert_eq!(last.get("part").unwrap().get::<f64>(), Some(&2.0));
+        assert!(last.get("nanos").unwrap().is_null());
+    }
+
+    #[test]
+    fn renders_markdown() {
+        let md = render(&get_mock_timings(), OutputFormat::Markdown).unwrap();
+        assert!(md.contains("| 1 | 10ms | 20µs | 10.02ms |"));
+        assert!(md.contains("| 3 | 1.5s | - | 1500.00ms |"));
+        assert!(md.contains("**Total: 1510.02ms**"));
+    }
+}